@@ -26,12 +26,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 1: Create a capability descriptor with server management permissions
     println!("1. Creating capability descriptor...");
-    let mut permission_descriptor: CapabilityDescriptor = CapabilityDescriptor::new();
-    permission_descriptor.insert("Administrator".to_string(), 0x1); // Binary: 00001
-    permission_descriptor.insert("ManageServer".to_string(), 0x2); // Binary: 00010
-    permission_descriptor.insert("ManageRoles".to_string(), 0x4); // Binary: 00100
-    permission_descriptor.insert("CreateInvitation".to_string(), 0x8); // Binary: 01000
-    permission_descriptor.insert("ManageChannels".to_string(), 0x10); // Binary: 10000
+    let permission_descriptor: CapabilityDescriptor = CapabilityDescriptor::builder()
+        .capability("Administrator", 0x1) // Binary: 00001
+        .capability("ManageServer", 0x2) // Binary: 00010
+        .capability("ManageRoles", 0x4) // Binary: 00100
+        .capability("CreateInvitation", 0x8) // Binary: 01000
+        .capability("ManageChannels", 0x10) // Binary: 10000
+        .build()?;
 
     println!("   Capabilities defined:");
     for (name, &hex_value) in &permission_descriptor {
@@ -72,10 +73,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Example 2: Combined permissions (Administrator + ManageServer)
-    let manage_server_value: CapabilityHexUnitValue = permission_descriptor
+    let manage_server_value: CapabilityHexUnitValue = *permission_descriptor
         .get("ManageServer")
-        .ok_or("ManageServer capability not found")?
-        .clone();
+        .ok_or("ManageServer capability not found")?;
 
     let combined_permission: CapilityHexValue = admin_value | manage_server_value;
     println!("   Combined Role (0x{:X}):", combined_permission);
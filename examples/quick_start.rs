@@ -17,11 +17,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Quick Start - Permission Translation Library\n");
 
     // 1. Define your permission system
-    let mut permissions = CapabilityDescriptor::new();
-    permissions.insert("Read".to_string(), 0x1);
-    permissions.insert("Write".to_string(), 0x2);
-    permissions.insert("Delete".to_string(), 0x4);
-    permissions.insert("Admin".to_string(), 0x8);
+    let permissions = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Delete", 0x4)
+        .capability("Admin", 0x8)
+        .build()?;
 
     println!("📋 Available permissions:");
     for (name, &value) in &permissions {
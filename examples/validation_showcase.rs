@@ -19,11 +19,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Example 1: Well-formed descriptor
     println!("1️⃣ Testing well-formed descriptor:");
-    let mut good_descriptor = CapabilityDescriptor::new();
-    good_descriptor.insert("Read".to_string(), 0x1);
-    good_descriptor.insert("Write".to_string(), 0x2);
-    good_descriptor.insert("Execute".to_string(), 0x4);
-    good_descriptor.insert("Admin".to_string(), 0x8);
+    let good_descriptor = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Execute", 0x4)
+        .capability("Admin", 0x8)
+        .build()?;
 
     test_descriptor("Good Descriptor", &good_descriptor);
 
@@ -62,7 +63,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n3️⃣ Testing edge cases:");
 
     // Empty descriptor
-    let empty_descriptor = CapabilityDescriptor::new();
+    let empty_descriptor = CapabilityDescriptor::default();
     println!("   Empty descriptor validation:");
     println!(
         "     - Value 0x0: {}",
@@ -82,8 +83,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // Single capability descriptor
-    let mut single_descriptor = CapabilityDescriptor::new();
-    single_descriptor.insert("OnlyCapability".to_string(), 0x1);
+    let single_descriptor = CapabilityDescriptor::builder()
+        .capability("OnlyCapability", 0x1)
+        .build()?;
     println!("\n   Single capability descriptor:");
     println!(
         "     - Value 0x0: {}",
@@ -112,12 +114,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Example 4: Large permission values
     println!("\n4️⃣ Testing with larger permission systems:");
-    let mut large_descriptor = CapabilityDescriptor::new();
+    let mut large_builder = CapabilityDescriptor::builder();
     for i in 0..10 {
         let permission_name = format!("Permission{}", i);
         let permission_value = 1 << i; // Powers of 2: 1, 2, 4, 8, 16, 32, 64, 128, 256, 512
-        large_descriptor.insert(permission_name, permission_value);
+        large_builder = large_builder.capability(permission_name, permission_value);
     }
+    let large_descriptor = large_builder.build()?;
 
     test_descriptor("Large Descriptor (10 permissions)", &large_descriptor);
 
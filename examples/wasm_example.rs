@@ -46,7 +46,7 @@ pub fn demo_permission_system() -> Result<JsValue, JsValue> {
     let max_value = js_get_max_hex_value_descriptor(&descriptor);
 
     // Create a role with Read + Write permissions
-    let role = JsRoleCapability::new(&descriptor, 0x3);
+    let role = JsRoleCapability::new(&descriptor, 0x3)?;
 
     // Create result object
     let result = js_sys::Object::new();
@@ -143,7 +143,7 @@ pub fn create_role_and_get_capabilities(
     permission_value: i32,
 ) -> Result<JsValue, JsValue> {
     let descriptor = JsCapabilityDescriptor::from_js_object(descriptor_obj)?;
-    let role = JsRoleCapability::new(&descriptor, permission_value);
+    let role = JsRoleCapability::new(&descriptor, permission_value)?;
 
     let result = js_sys::Object::new();
 
@@ -182,11 +182,13 @@ fn main() {
     println!();
 
     // Create a capability descriptor
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("Read".to_string(), 0x1);
-    descriptor.insert("Write".to_string(), 0x2);
-    descriptor.insert("Execute".to_string(), 0x4);
-    descriptor.insert("Admin".to_string(), 0x8);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Execute", 0x4)
+        .capability("Admin", 0x8)
+        .build()
+        .expect("descriptor values are distinct single bits");
 
     println!(
         "📋 Created descriptor with {} capabilities:",
@@ -0,0 +1,375 @@
+//! # Algebra Module
+//!
+//! This module adds set operations to [`RoleCapability`]: union, intersection, difference
+//! and complement, plus subset and superset checks. Each operation is available as a
+//! method that returns an error when the two roles use different descriptors, and as an
+//! operator (`|`, `&`, `-`, `!`) that panics in that case.
+//!
+//! Level fields are compared by level rather than by bits: the union holds the higher of
+//! the two levels, the intersection the lower, and the difference keeps a level only if
+//! it is higher than the other role's. A role is a subset of another only if each of its
+//! levels is at most the other role's. Levels have no complement, so `!` leaves them
+//! unchanged.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Write", 0x2)
+//!     .capability("Delete", 0x4)
+//!     .build()
+//!     .unwrap();
+//!
+//! let reader = RoleCapability::new(descriptor.clone(), 0x1);
+//! let editor = RoleCapability::new(descriptor, 0x3);
+//!
+//! assert_eq!((&reader | &editor).hex_value, 0x3);
+//! assert_eq!((&editor - &reader).hex_value, 0x2);
+//! assert_eq!((!&editor).hex_value, 0x4);
+//! assert_eq!(reader.is_subset_of(&editor), Ok(true));
+//! ```
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+use crate::checks::get_max_hex_value_descriptor;
+use crate::level::{
+    field_mask, intersect_levels, levels_within, subtract_levels, union_levels, LevelField,
+};
+use crate::role_capability::RoleCapability;
+use crate::value::PermissionValue;
+
+/// Error returned when a set operation combines roles that use different descriptors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorMismatchError;
+
+impl fmt::Display for DescriptorMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "roles use different capability descriptors")
+    }
+}
+
+impl std::error::Error for DescriptorMismatchError {}
+
+impl<V: PermissionValue> RoleCapability<V> {
+    /// Returns a role with the capabilities of either role.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DescriptorMismatchError`] if the roles use different descriptors.
+    pub fn union(&self, other: &Self) -> Result<Self, DescriptorMismatchError> {
+        self.combine(other, union_levels)
+    }
+
+    /// Returns a role with the capabilities present in both roles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DescriptorMismatchError`] if the roles use different descriptors.
+    pub fn intersection(&self, other: &Self) -> Result<Self, DescriptorMismatchError> {
+        self.combine(other, intersect_levels)
+    }
+
+    /// Returns a role with the capabilities of `self` that `other` does not have.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DescriptorMismatchError`] if the roles use different descriptors.
+    pub fn difference(&self, other: &Self) -> Result<Self, DescriptorMismatchError> {
+        self.combine(other, subtract_levels)
+    }
+
+    /// Returns a role with every capability of the descriptor that `self` does not have.
+    ///
+    /// The complement is limited to the descriptor's mask, so bits that no capability
+    /// defines are never set in the result. Level fields have no complement: they keep
+    /// the level of `self` unchanged.
+    pub fn complement(&self) -> Self {
+        let descriptor = self.descriptor();
+        let fields = field_mask(descriptor.level_fields());
+        let flags = get_max_hex_value_descriptor(descriptor).and_not(&fields);
+        let value = flags
+            .and_not(&self.hex_value)
+            .or(&self.hex_value.and(&fields));
+        RoleCapability::new(descriptor.clone(), value)
+    }
+
+    /// Returns `true` if every capability of `self` is also held by `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DescriptorMismatchError`] if the roles use different descriptors.
+    pub fn is_subset_of(&self, other: &Self) -> Result<bool, DescriptorMismatchError> {
+        self.check_same_descriptor(other)?;
+        Ok(levels_within(
+            self.descriptor().level_fields(),
+            &self.hex_value,
+            &other.hex_value,
+        ))
+    }
+
+    /// Returns `true` if `self` holds every capability of `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DescriptorMismatchError`] if the roles use different descriptors.
+    pub fn is_superset_of(&self, other: &Self) -> Result<bool, DescriptorMismatchError> {
+        other.is_subset_of(self)
+    }
+
+    fn check_same_descriptor(&self, other: &Self) -> Result<(), DescriptorMismatchError> {
+        if self.descriptor() == other.descriptor() {
+            Ok(())
+        } else {
+            Err(DescriptorMismatchError)
+        }
+    }
+
+    fn combine(
+        &self,
+        other: &Self,
+        operation: impl Fn(&[LevelField<V>], &V, &V) -> V,
+    ) -> Result<Self, DescriptorMismatchError> {
+        self.check_same_descriptor(other)?;
+        let descriptor = self.descriptor();
+        Ok(RoleCapability::new(
+            descriptor.clone(),
+            operation(descriptor.level_fields(), &self.hex_value, &other.hex_value),
+        ))
+    }
+}
+
+macro_rules! impl_binary_operator {
+    ($trait:ident, $method:ident, $operation:ident) => {
+        impl<V: PermissionValue> $trait for &RoleCapability<V> {
+            type Output = RoleCapability<V>;
+
+            #[doc = concat!(
+                        "Returns the [`", stringify!($operation), "`](RoleCapability::",
+                        stringify!($operation), ") of the two roles.\n\n",
+                        "# Panics\n\n",
+                        "Panics if the roles use different descriptors. Use [`",
+                        stringify!($operation), "`](RoleCapability::", stringify!($operation),
+                        ") for a fallible version."
+                    )]
+            fn $method(self, other: Self) -> RoleCapability<V> {
+                self.$operation(other)
+                    .expect("set operation on roles with different descriptors")
+            }
+        }
+
+        impl<V: PermissionValue> $trait for RoleCapability<V> {
+            type Output = RoleCapability<V>;
+
+            #[doc = concat!(
+                        "Returns the [`", stringify!($operation), "`](RoleCapability::",
+                        stringify!($operation), ") of the two roles.\n\n",
+                        "# Panics\n\n",
+                        "Panics if the roles use different descriptors. Use [`",
+                        stringify!($operation), "`](RoleCapability::", stringify!($operation),
+                        ") for a fallible version."
+                    )]
+            fn $method(self, other: Self) -> RoleCapability<V> {
+                (&self).$method(&other)
+            }
+        }
+    };
+}
+
+impl_binary_operator!(BitOr, bitor, union);
+impl_binary_operator!(BitAnd, bitand, intersection);
+impl_binary_operator!(Sub, sub, difference);
+
+impl<V: PermissionValue> Not for &RoleCapability<V> {
+    type Output = RoleCapability<V>;
+
+    fn not(self) -> RoleCapability<V> {
+        self.complement()
+    }
+}
+
+impl<V: PermissionValue> Not for RoleCapability<V> {
+    type Output = RoleCapability<V>;
+
+    fn not(self) -> RoleCapability<V> {
+        self.complement()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CapabilityDescriptor;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .build()
+            .unwrap()
+    }
+
+    fn role(value: i32) -> RoleCapability {
+        RoleCapability::new(create_test_descriptor(), value)
+    }
+
+    #[test]
+    fn test_union_intersection_difference() {
+        let a = role(0x3);
+        let b = role(0x6);
+
+        assert_eq!(a.union(&b).unwrap().hex_value, 0x7);
+        assert_eq!(a.intersection(&b).unwrap().hex_value, 0x2);
+        assert_eq!(a.difference(&b).unwrap().hex_value, 0x1);
+        assert_eq!(b.difference(&a).unwrap().hex_value, 0x4);
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = role(0x3);
+        let b = role(0x6);
+
+        assert_eq!(&a | &b, role(0x7));
+        assert_eq!(&a & &b, role(0x2));
+        assert_eq!(&a - &b, role(0x1));
+        assert_eq!(!&a, role(0xC));
+
+        // Owned operands chain naturally
+        assert_eq!(a.clone() | b.clone() | role(0x8), role(0xF));
+        assert_eq!(!(a - b), role(0xE));
+    }
+
+    #[test]
+    fn test_complement_is_limited_to_mask() {
+        let sparse = CapabilityDescriptor::builder()
+            .capability("Low", 0x1)
+            .capability("High", 0x100)
+            .build()
+            .unwrap();
+
+        let low = RoleCapability::new(sparse.clone(), 0x1);
+        assert_eq!(low.complement().hex_value, 0x100);
+
+        // Undefined bits in the value are dropped by the complement
+        let with_unknown = RoleCapability::new(sparse, 0x11);
+        assert_eq!(with_unknown.complement().hex_value, 0x100);
+
+        assert_eq!(role(0x0).complement().hex_value, 0xF);
+        assert_eq!(role(0xF).complement().hex_value, 0x0);
+    }
+
+    #[test]
+    fn test_subset_and_superset() {
+        let reader = role(0x1);
+        let editor = role(0x3);
+
+        assert_eq!(reader.is_subset_of(&editor), Ok(true));
+        assert_eq!(editor.is_subset_of(&reader), Ok(false));
+        assert_eq!(editor.is_superset_of(&reader), Ok(true));
+        assert_eq!(reader.is_superset_of(&editor), Ok(false));
+
+        // Every role is a subset and superset of itself
+        assert_eq!(editor.is_subset_of(&editor), Ok(true));
+        assert_eq!(editor.is_superset_of(&editor), Ok(true));
+        assert_eq!(role(0x0).is_subset_of(&reader), Ok(true));
+    }
+
+    #[test]
+    fn test_different_descriptors_fail() {
+        let other_descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .build()
+            .unwrap();
+        let a = role(0x1);
+        let b = RoleCapability::new(other_descriptor, 0x1);
+
+        assert_eq!(a.union(&b), Err(DescriptorMismatchError));
+        assert_eq!(a.intersection(&b), Err(DescriptorMismatchError));
+        assert_eq!(a.difference(&b), Err(DescriptorMismatchError));
+        assert_eq!(a.is_subset_of(&b), Err(DescriptorMismatchError));
+        assert_eq!(a.is_superset_of(&b), Err(DescriptorMismatchError));
+        assert_eq!(
+            DescriptorMismatchError.to_string(),
+            "roles use different capability descriptors"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "different descriptors")]
+    fn test_operator_panics_on_different_descriptors() {
+        let other_descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .build()
+            .unwrap();
+        let _ = role(0x1) | RoleCapability::new(other_descriptor, 0x1);
+    }
+
+    #[test]
+    fn test_levels_combine_by_level() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Login", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+        let reader = RoleCapability::from_names(descriptor.clone(), ["FileAccess=read"]).unwrap();
+        let writer =
+            RoleCapability::from_names(descriptor.clone(), ["Login", "FileAccess=write"]).unwrap();
+
+        // read (0b01) | write (0b10) is write, not admin (0b11)
+        let union = reader.union(&writer).unwrap();
+        assert_eq!(union.hex_value, 0x5);
+        assert_eq!(union.level("FileAccess").map(String::as_str), Some("write"));
+
+        assert_eq!(reader.intersection(&writer).unwrap().hex_value, 0x2);
+        assert_eq!(writer.difference(&reader).unwrap().hex_value, 0x5);
+        assert_eq!(reader.difference(&writer).unwrap().hex_value, 0x0);
+
+        assert_eq!(reader.is_subset_of(&writer), Ok(true));
+        assert_eq!(writer.is_subset_of(&reader), Ok(false));
+        assert_eq!(writer.is_superset_of(&reader), Ok(true));
+    }
+
+    #[test]
+    fn test_complement_keeps_levels() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .capability("Send", 0x8)
+            .build()
+            .unwrap();
+
+        let viewer = RoleCapability::new(descriptor.clone(), 0x1);
+        assert_eq!(viewer.complement().hex_value, 0x8);
+
+        let writer = RoleCapability::from_names(descriptor, ["View", "FileAccess=write"]).unwrap();
+        let complement = writer.complement();
+        assert_eq!(complement.hex_value, 0xC);
+        assert_eq!(
+            complement.level("FileAccess").map(String::as_str),
+            Some("write")
+        );
+    }
+
+    #[test]
+    fn test_wide_values() {
+        let descriptor = CapabilityDescriptor::<u128>::builder()
+            .capability("Low", 0x1)
+            .capability("High", 1 << 100)
+            .build()
+            .unwrap();
+        let low = RoleCapability::new(descriptor.clone(), 0x1);
+        let high = RoleCapability::new(descriptor, 1 << 100);
+
+        assert_eq!((&low | &high).hex_value, (1 << 100) | 0x1);
+        assert_eq!((!&low).hex_value, 1 << 100);
+    }
+}
@@ -0,0 +1,476 @@
+//! # Bitset Module
+//!
+//! This module provides [`CapabilityBitSet`], a growable permission value for permission
+//! systems with more capabilities than any fixed-width integer can hold.
+//!
+//! A `CapabilityBitSet` implements [`PermissionValue`], so it can be used anywhere an
+//! integer value can: as the value type of a [`CapabilityDescriptor`](crate::models::CapabilityDescriptor),
+//! in a [`RoleCapability`](crate::role_capability::RoleCapability) and with
+//! [`is_valid_hex`](crate::checks::is_valid_hex).
+//!
+//! ## Hex Representation
+//!
+//! Bitsets are written as hexadecimal strings of any length, with an optional `0x` prefix:
+//!
+//! ```rust
+//! use permission_translation::bitset::CapabilityBitSet;
+//!
+//! let value: CapabilityBitSet = "0x1_0000000000000000_0000000000000001".parse().unwrap();
+//! assert!(value.contains(0));
+//! assert!(value.contains(128));
+//! assert_eq!(value.to_string(), "0x100000000000000000000000000000001");
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::value::PermissionValue;
+
+const WORD_BITS: u32 = u64::BITS;
+
+/// A growable set of capability bits.
+///
+/// Bits are stored in little-endian `u64` words with no trailing zero words, so two
+/// bitsets holding the same bits are always equal and hash identically. Ordering
+/// compares the bitsets as unsigned integers.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{
+///     bitset::CapabilityBitSet,
+///     checks::is_valid_hex,
+///     models::CapabilityDescriptor,
+///     role_capability::RoleCapability,
+/// };
+///
+/// let descriptor = (0..300)
+///     .fold(CapabilityDescriptor::<CapabilityBitSet>::builder(), |builder, i| {
+///         builder.next_capability(format!("Capability{}", i))
+///     })
+///     .build()
+///     .unwrap();
+///
+/// let value: CapabilityBitSet = [3, 299].into_iter().collect();
+/// assert!(is_valid_hex(value.clone(), &descriptor));
+///
+/// let role = RoleCapability::new(descriptor, value);
+/// assert!(role.has_capability(&"Capability299".to_string()));
+/// assert_eq!(role.to_name_set().len(), 2);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct CapabilityBitSet {
+    words: Vec<u64>,
+}
+
+impl CapabilityBitSet {
+    /// Creates an empty bitset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a bitset with only the bit at `index` set.
+    pub fn from_bit(index: u32) -> Self {
+        let mut bitset = Self::new();
+        bitset.insert(index);
+        bitset
+    }
+
+    /// Sets the bit at `index`, growing the bitset if needed.
+    pub fn insert(&mut self, index: u32) {
+        let word = (index / WORD_BITS) as usize;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (index % WORD_BITS);
+    }
+
+    /// Clears the bit at `index`.
+    pub fn remove(&mut self, index: u32) {
+        let word = (index / WORD_BITS) as usize;
+        if let Some(w) = self.words.get_mut(word) {
+            *w &= !(1 << (index % WORD_BITS));
+        }
+        self.normalize();
+    }
+
+    /// Returns `true` if the bit at `index` is set.
+    pub fn contains(&self, index: u32) -> bool {
+        self.words
+            .get((index / WORD_BITS) as usize)
+            .is_some_and(|w| (w >> (index % WORD_BITS)) & 1 == 1)
+    }
+
+    /// Returns the underlying little-endian words.
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Creates a bitset from little-endian words.
+    pub fn from_words(words: Vec<u64>) -> Self {
+        let mut bitset = CapabilityBitSet { words };
+        bitset.normalize();
+        bitset
+    }
+
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    fn zip_words(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.words.len().max(other.words.len());
+        let words = (0..len)
+            .map(|i| {
+                f(
+                    self.words.get(i).copied().unwrap_or(0),
+                    other.words.get(i).copied().unwrap_or(0),
+                )
+            })
+            .collect();
+        Self::from_words(words)
+    }
+
+    fn mul_add_small(&mut self, mul: u64, add: u64) {
+        let mut carry = add as u128;
+        for word in &mut self.words {
+            let product = (*word as u128) * (mul as u128) + carry;
+            *word = product as u64;
+            carry = product >> WORD_BITS;
+        }
+        if carry != 0 {
+            self.words.push(carry as u64);
+        }
+        self.normalize();
+    }
+}
+
+impl PermissionValue for CapabilityBitSet {
+    const BITS: Option<u32> = None;
+
+    fn bit(index: u32) -> Option<Self> {
+        Some(Self::from_bit(index))
+    }
+
+    fn test_bit(&self, index: u32) -> bool {
+        self.contains(index)
+    }
+
+    fn bit_len(&self) -> u32 {
+        match self.words.last() {
+            Some(last) => self.words.len() as u32 * WORD_BITS - last.leading_zeros(),
+            None => 0,
+        }
+    }
+
+    fn or(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a | b)
+    }
+
+    fn and(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & b)
+    }
+
+    fn and_not(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & !b)
+    }
+
+    fn overflowing_add(&self, other: &Self) -> (Self, bool) {
+        let len = self.words.len().max(other.words.len());
+        let mut words = Vec::with_capacity(len + 1);
+        let mut carry = false;
+        for i in 0..len {
+            let a = self.words.get(i).copied().unwrap_or(0);
+            let b = other.words.get(i).copied().unwrap_or(0);
+            let (sum, c1) = a.overflowing_add(b);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            words.push(sum);
+            carry = c1 || c2;
+        }
+        if carry {
+            words.push(1);
+        }
+        (Self::from_words(words), false)
+    }
+
+    fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+        if digits.is_empty() {
+            return None;
+        }
+        let mut bitset = Self::new();
+        for c in digits.chars() {
+            let digit = c.to_digit(radix)?;
+            bitset.mul_add_small(radix as u64, digit as u64);
+        }
+        Some(bitset)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn count_ones(&self) -> u32 {
+        self.words.iter().map(|w| w.count_ones()).sum()
+    }
+}
+
+impl Ord for CapabilityBitSet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words
+            .len()
+            .cmp(&other.words.len())
+            .then_with(|| self.words.iter().rev().cmp(other.words.iter().rev()))
+    }
+}
+
+impl PartialOrd for CapabilityBitSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromIterator<u32> for CapabilityBitSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut bitset = Self::new();
+        for index in iter {
+            bitset.insert(index);
+        }
+        bitset
+    }
+}
+
+impl From<u64> for CapabilityBitSet {
+    fn from(value: u64) -> Self {
+        Self::from_words(vec![value])
+    }
+}
+
+impl From<u128> for CapabilityBitSet {
+    fn from(value: u128) -> Self {
+        Self::from_words(vec![value as u64, (value >> WORD_BITS) as u64])
+    }
+}
+
+impl fmt::UpperHex for CapabilityBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = match self.words.last() {
+            Some(last) => format!("{:X}", last),
+            None => "0".to_string(),
+        };
+        for word in self.words.iter().rev().skip(1) {
+            digits.push_str(&format!("{:016X}", word));
+        }
+        f.pad_integral(true, "0x", &digits)
+    }
+}
+
+impl fmt::LowerHex for CapabilityBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:X}", self).to_lowercase();
+        f.pad_integral(true, "0x", &digits)
+    }
+}
+
+impl fmt::Display for CapabilityBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#X}", self)
+    }
+}
+
+impl fmt::Debug for CapabilityBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CapabilityBitSet({:#X})", self)
+    }
+}
+
+/// Error returned when a string is not a valid hexadecimal bitset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBitSetError {
+    /// The input that failed to parse.
+    pub input: String,
+}
+
+impl fmt::Display for ParseBitSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid hexadecimal bitset: {:?}", self.input)
+    }
+}
+
+impl std::error::Error for ParseBitSetError {}
+
+impl FromStr for CapabilityBitSet {
+    type Err = ParseBitSetError;
+
+    /// Parses a hexadecimal string of any length. A `0x` prefix and `_` separators are allowed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let digits = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+            .unwrap_or(trimmed)
+            .replace('_', "");
+        <Self as PermissionValue>::from_str_radix(&digits, 16).ok_or_else(|| ParseBitSetError {
+            input: s.to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CapabilityBitSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CapabilityBitSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::{get_max_hex_value_descriptor, is_valid_hex};
+    use crate::models::CapabilityDescriptor;
+    use crate::role_capability::RoleCapability;
+
+    fn create_large_descriptor(size: u32) -> CapabilityDescriptor<CapabilityBitSet> {
+        (0..size)
+            .fold(CapabilityDescriptor::builder(), |builder, i| {
+                builder.capability(format!("Capability{}", i), CapabilityBitSet::from_bit(i))
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut bitset = CapabilityBitSet::new();
+        assert!(bitset.is_zero());
+
+        bitset.insert(5);
+        bitset.insert(200);
+        assert!(bitset.contains(5));
+        assert!(bitset.contains(200));
+        assert!(!bitset.contains(6));
+        assert_eq!(bitset.bit_len(), 201);
+
+        bitset.remove(200);
+        assert_eq!(bitset.as_words(), &[0x20]);
+        assert_eq!(bitset, CapabilityBitSet::from(0x20u64));
+    }
+
+    #[test]
+    fn test_bitwise_operations() {
+        let a: CapabilityBitSet = [1, 70, 130].into_iter().collect();
+        let b: CapabilityBitSet = [70, 300].into_iter().collect();
+
+        assert_eq!(a.or(&b).bit_positions(), vec![1, 70, 130, 300]);
+        assert_eq!(a.and(&b).bit_positions(), vec![70]);
+        assert_eq!(a.and_not(&b).bit_positions(), vec![1, 130]);
+        assert!(a.intersects(&b));
+        assert!(!a.contains_all(&b));
+        assert_eq!(
+            a.and(&CapabilityBitSet::from_bit(300)),
+            CapabilityBitSet::new()
+        );
+    }
+
+    #[test]
+    fn test_ordering_is_numeric() {
+        let small = CapabilityBitSet::from(u64::MAX);
+        let large = CapabilityBitSet::from_bit(64);
+        assert!(small < large);
+        assert!(CapabilityBitSet::from(2u64) > CapabilityBitSet::from(1u64));
+        assert_eq!(
+            CapabilityBitSet::from_words(vec![1, 0, 0]).cmp(&CapabilityBitSet::from(1u64)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_addition_carries() {
+        let a = CapabilityBitSet::from(u64::MAX);
+        let (sum, overflowed) = a.overflowing_add(&CapabilityBitSet::from(1u64));
+        assert!(!overflowed);
+        assert_eq!(sum, CapabilityBitSet::from_bit(64));
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let value: CapabilityBitSet = [0, 64, 255].into_iter().collect();
+        let hex = value.to_string();
+        assert_eq!(
+            hex,
+            concat!(
+                "0x8000000000000000",
+                "0000000000000000",
+                "0000000000000001",
+                "0000000000000001"
+            )
+        );
+        assert_eq!(hex.parse::<CapabilityBitSet>().unwrap(), value);
+
+        assert_eq!(CapabilityBitSet::new().to_string(), "0x0");
+        assert_eq!(
+            "0".parse::<CapabilityBitSet>().unwrap(),
+            CapabilityBitSet::new()
+        );
+        assert_eq!(
+            "ff".parse::<CapabilityBitSet>().unwrap(),
+            CapabilityBitSet::from(0xFFu64)
+        );
+        assert_eq!(format!("{:x}", CapabilityBitSet::from(0xABu64)), "ab");
+        assert_eq!(
+            format!("{:#06X}", CapabilityBitSet::from(0xABu64)),
+            "0x00AB"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("".parse::<CapabilityBitSet>().is_err());
+        assert!("0x".parse::<CapabilityBitSet>().is_err());
+        let err = "0xZZ".parse::<CapabilityBitSet>().unwrap_err();
+        assert_eq!(err.input, "0xZZ");
+    }
+
+    #[test]
+    fn test_from_str_radix_decimal() {
+        let value = <CapabilityBitSet as PermissionValue>::from_str_radix(
+            "340282366920938463463374607431768211456",
+            10,
+        )
+        .unwrap();
+        assert_eq!(value, CapabilityBitSet::from_bit(128));
+    }
+
+    #[test]
+    fn test_validation_with_large_descriptor() {
+        let descriptor = create_large_descriptor(500);
+        let max_value = get_max_hex_value_descriptor(&descriptor);
+        assert_eq!(PermissionValue::count_ones(&max_value), 500);
+
+        assert!(is_valid_hex(max_value.clone(), &descriptor));
+        assert!(is_valid_hex(CapabilityBitSet::from_bit(499), &descriptor));
+        assert!(!is_valid_hex(CapabilityBitSet::from_bit(500), &descriptor));
+    }
+
+    #[test]
+    fn test_role_with_large_descriptor() {
+        let descriptor = create_large_descriptor(400);
+        let value: CapabilityBitSet = [0, 150, 399].into_iter().collect();
+        let role = RoleCapability::new(descriptor, value);
+
+        let name_set = role.to_name_set();
+        assert_eq!(name_set.len(), 3);
+        assert!(name_set.contains("Capability150"));
+        assert!(role.has_capability(&"Capability399".to_string()));
+        assert!(!role.has_capability(&"Capability398".to_string()));
+    }
+}
@@ -6,7 +6,11 @@
 //! ## Validation Functions
 //!
 //! - [`is_valid_hex`]: Validates a permission value against a capability descriptor
+//! - [`validate`]: Same checks as [`is_valid_hex`], returning a [`ValidationError`] on failure
 //! - [`get_max_hex_value_descriptor`]: Calculates the maximum possible permission value for a descriptor
+//! - [`checked_sum_hex_value_descriptor`]: Sums the descriptor values, detecting overflow
+//!
+//! For a full integrity report of a descriptor, see [`lint_descriptor`](crate::lint::lint_descriptor).
 //!
 //! ## Validation Rules
 //!
@@ -14,11 +18,18 @@
 //! 1. **Descriptor Integrity**: The OR mask of descriptor values must not exceed their sum
 //! 2. **Valid Bits Only**: Permission values can only have bits set that are defined in the descriptor
 //! 3. **Maximum Permission**: Permission values cannot exceed the maximum allowed by the descriptor
+//! 4. **Valid Levels**: The bits of each [level field](crate::level) must hold one of its levels
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
-use crate::models::{CapabilityDescriptor, CapilityHexValue};
+use std::fmt;
+
+use crate::descriptor::DescriptorLike;
+use crate::models::CapabilityName;
+#[cfg(feature = "wasm")]
+use crate::models::CapilityHexValue;
+use crate::value::PermissionValue;
 
 /// Validates a hexadecimal permission value against a capability descriptor.
 ///
@@ -30,7 +41,9 @@ use crate::models::{CapabilityDescriptor, CapilityHexValue};
 /// # Arguments
 ///
 /// * `value` - The hexadecimal permission value to validate
-/// * `descriptor` - The capability descriptor defining valid permissions
+/// * `descriptor` - The capability descriptor defining valid permissions, either a validated
+///   [`CapabilityDescriptor`](crate::models::CapabilityDescriptor) or a raw
+///   [`CapabilityMap`](crate::models::CapabilityMap)
 ///
 /// # Returns
 ///
@@ -42,9 +55,11 @@ use crate::models::{CapabilityDescriptor, CapilityHexValue};
 /// ```rust
 /// use permission_translation::{models::CapabilityDescriptor, checks::is_valid_hex};
 ///
-/// let mut descriptor = CapabilityDescriptor::new();
-/// descriptor.insert("Admin".to_string(), 0x1);
-/// descriptor.insert("User".to_string(), 0x2);
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Admin", 0x1)
+///     .capability("User", 0x2)
+///     .build()
+///     .unwrap();
 ///
 /// // Valid permission values
 /// assert!(is_valid_hex(0x1, &descriptor)); // Admin only
@@ -69,36 +84,246 @@ use crate::models::{CapabilityDescriptor, CapilityHexValue};
 /// ## Maximum Permission Check
 /// Ensures the permission value doesn't exceed the theoretical maximum (all permissions combined).
 /// This prevents values that might be mathematically valid but exceed intended limits.
+///
+/// # Value Types
+///
+/// The function is generic over [`PermissionValue`], so it works the same way for
+/// `i32`, `u32`, `u64` and `u128` descriptors. An arithmetic overflow while summing
+/// the descriptor values is treated as a descriptor integrity failure.
+pub fn is_valid_hex<D: DescriptorLike>(value: D::Value, descriptor: &D) -> bool {
+    validate(&value, descriptor).is_ok()
+}
 
-pub fn is_valid_hex(value: CapilityHexValue, descriptor: &CapabilityDescriptor) -> bool {
-    // Combine all unit values from the descriptor
-    // to form a mask of valid bits.
-    let mut combined_value: CapilityHexValue = 0;
-    let mut sum_value: CapilityHexValue = 0;
-    for &unit_value in descriptor.values() {
-        combined_value |= unit_value;
-        sum_value += unit_value;
+/// The validation rule that rejected a permission value.
+///
+/// Returned by [`ValidationError::rule`] so callers can branch on the cause of a
+/// failure without matching on the error's fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationRule {
+    /// The descriptor itself is corrupted (OR mask exceeds the sum, the sum overflows,
+    /// or an entry is negative).
+    DescriptorIntegrity,
+    /// The permission value is negative (sign bit set on a signed value type).
+    NegativeValue,
+    /// The permission value has bits set that no descriptor entry defines.
+    UnknownBits,
+    /// The bits of a level field do not match any of its levels.
+    InvalidLevel,
+}
+
+impl fmt::Display for ValidationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValidationRule::DescriptorIntegrity => "descriptor integrity",
+            ValidationRule::NegativeValue => "negative value",
+            ValidationRule::UnknownBits => "unknown bits",
+            ValidationRule::InvalidLevel => "invalid level",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Error returned by [`validate`] when a permission value is rejected.
+///
+/// Each variant corresponds to one [`ValidationRule`] and carries the bit positions
+/// that caused the failure, in ascending order.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{
+///     checks::{validate, ValidationError, ValidationRule},
+///     models::CapabilityDescriptor,
+/// };
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .build()
+///     .unwrap();
+///
+/// let error = validate(&0x6, &descriptor).unwrap_err();
+/// assert_eq!(error, ValidationError::UnknownBits { bits: vec![1, 2] });
+/// assert_eq!(error.rule(), ValidationRule::UnknownBits);
+/// assert_eq!(error.to_string(), "unknown bits: bit(s) 1, 2 are not defined in the descriptor");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The descriptor failed its integrity check. `bits` lists the bits shared by
+    /// more than one entry and the sign bit of any negative entry.
+    DescriptorIntegrity { bits: Vec<u32> },
+    /// The value is negative. `bits` holds the sign bit position.
+    NegativeValue { bits: Vec<u32> },
+    /// The value uses bits outside the descriptor mask. `bits` lists those bits.
+    UnknownBits { bits: Vec<u32> },
+    /// The bits of a level field do not match a level. `bits` lists the field's set bits.
+    InvalidLevel {
+        field: CapabilityName,
+        bits: Vec<u32>,
+    },
+}
+
+impl ValidationError {
+    /// Returns the rule that failed.
+    pub fn rule(&self) -> ValidationRule {
+        match self {
+            ValidationError::DescriptorIntegrity { .. } => ValidationRule::DescriptorIntegrity,
+            ValidationError::NegativeValue { .. } => ValidationRule::NegativeValue,
+            ValidationError::UnknownBits { .. } => ValidationRule::UnknownBits,
+            ValidationError::InvalidLevel { .. } => ValidationRule::InvalidLevel,
+        }
+    }
+
+    /// Returns the offending bit positions, in ascending order.
+    pub fn bits(&self) -> &[u32] {
+        match self {
+            ValidationError::DescriptorIntegrity { bits }
+            | ValidationError::NegativeValue { bits }
+            | ValidationError::UnknownBits { bits }
+            | ValidationError::InvalidLevel { bits, .. } => bits,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = self
+            .bits()
+            .iter()
+            .map(|bit| bit.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self {
+            ValidationError::DescriptorIntegrity { .. } => write!(
+                f,
+                "{}: descriptor values overlap or overflow at bit(s) {}",
+                self.rule(),
+                bits
+            ),
+            ValidationError::NegativeValue { .. } => {
+                write!(f, "{}: sign bit {} is set", self.rule(), bits)
+            }
+            ValidationError::UnknownBits { .. } => write!(
+                f,
+                "{}: bit(s) {} are not defined in the descriptor",
+                self.rule(),
+                bits
+            ),
+            ValidationError::InvalidLevel { field, .. } => write!(
+                f,
+                "{}: bit(s) {} of field {:?} do not match a level",
+                self.rule(),
+                bits,
+                field
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Validates a permission value against a capability descriptor, reporting why it failed.
+///
+/// This is the structured counterpart of [`is_valid_hex`]: both apply the same rules,
+/// and `is_valid_hex(value, descriptor)` is `true` exactly when this function returns `Ok(())`.
+/// The rules are checked in this order and the first failure is returned:
+///
+/// 1. [`ValidationRule::DescriptorIntegrity`]: the descriptor's OR mask must not exceed the sum
+///    of its values, the sum must not overflow, and no entry may be negative
+/// 2. [`ValidationRule::NegativeValue`]: the value must not be negative
+/// 3. [`ValidationRule::UnknownBits`]: the value may only use bits defined in the descriptor,
+///    by a capability or a level field
+/// 4. [`ValidationRule::InvalidLevel`]: the bits of each level field must hold one of its levels
+///
+/// # Arguments
+///
+/// * `value` - The permission value to validate
+/// * `descriptor` - The capability descriptor defining valid permissions
+///
+/// # Returns
+///
+/// * `Ok(())` if the value is valid
+/// * `Err(ValidationError)` describing the failed rule and the offending bit positions
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{
+///     checks::{validate, ValidationRule},
+///     models::CapabilityDescriptor,
+/// };
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("Write", 0x2)
+///     .build()
+///     .unwrap();
+///
+/// assert!(validate(&0x3, &descriptor).is_ok());
+///
+/// let error = validate(&-1, &descriptor).unwrap_err();
+/// assert_eq!(error.rule(), ValidationRule::NegativeValue);
+/// assert_eq!(error.bits(), &[31]);
+/// ```
+pub fn validate<D: DescriptorLike>(
+    value: &D::Value,
+    descriptor: &D,
+) -> Result<(), ValidationError> {
+    // Combine all unit values from the descriptor to form a mask of valid bits,
+    // tracking bits that appear in more than one entry along the way.
+    let mut combined_value = D::Value::zero();
+    let mut overlapping_value = D::Value::zero();
+    let mut sum_value = Some(D::Value::zero());
+    let mut negative_bits = Vec::new();
+    for unit_value in descriptor.capability_map().values() {
+        overlapping_value = overlapping_value.or(&combined_value.and(unit_value));
+        combined_value = combined_value.or(unit_value);
+        sum_value = sum_value.and_then(|sum| sum.checked_add(unit_value));
+        if unit_value.is_negative() {
+            negative_bits.push(unit_value.bit_len() - 1);
+        }
     }
 
     // Check that the mask doesn't exceed the sum of descriptor values
     // This validates the integrity of the descriptor data
-    if combined_value > sum_value {
-        return false;
+    let integrity_ok = match &sum_value {
+        Some(sum) => combined_value <= *sum,
+        None => false,
+    };
+    if !integrity_ok || !negative_bits.is_empty() {
+        let mut bits = overlapping_value.bit_positions();
+        bits.extend(negative_bits);
+        bits.sort_unstable();
+        bits.dedup();
+        return Err(ValidationError::DescriptorIntegrity { bits });
+    }
+
+    if value.is_negative() {
+        return Err(ValidationError::NegativeValue {
+            bits: vec![value.bit_len() - 1],
+        });
     }
 
     // Check if the provided value has any bits
-    // set outside of this mask.
-    if (value & !combined_value) != 0 {
-        return false;
+    // set outside of this mask, counting the bits of level fields as defined.
+    let fields = descriptor.level_fields();
+    let field_mask = fields
+        .iter()
+        .fold(D::Value::zero(), |mask, field| mask.or(field.mask()));
+    let unknown_value = value.and_not(&combined_value.or(&field_mask));
+    if !unknown_value.is_zero() {
+        return Err(ValidationError::UnknownBits {
+            bits: unknown_value.bit_positions(),
+        });
     }
 
-    // Check if the value exceeds the maximum permission
-    // allowed by the descriptor (which is the combination of all permissions)
-    if value > combined_value {
-        return false;
+    if let Some(field) = fields.iter().find(|field| field.decode(value).is_none()) {
+        return Err(ValidationError::InvalidLevel {
+            field: field.name().clone(),
+            bits: value.and(field.mask()).bit_positions(),
+        });
     }
 
-    true
+    Ok(())
 }
 
 /// Calculates the maximum possible hexadecimal permission value for a given descriptor.
@@ -120,10 +345,12 @@ pub fn is_valid_hex(value: CapilityHexValue, descriptor: &CapabilityDescriptor)
 /// ```rust
 /// use permission_translation::{models::CapabilityDescriptor, checks::get_max_hex_value_descriptor};
 ///
-/// let mut descriptor = CapabilityDescriptor::new();
-/// descriptor.insert("Read".to_string(), 0x1);    // Binary: 0001
-/// descriptor.insert("Write".to_string(), 0x2);   // Binary: 0010
-/// descriptor.insert("Execute".to_string(), 0x4); // Binary: 0100
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)    // Binary: 0001
+///     .capability("Write", 0x2)   // Binary: 0010
+///     .capability("Execute", 0x4) // Binary: 0100
+///     .build()
+///     .unwrap();
 ///
 /// let max_value = get_max_hex_value_descriptor(&descriptor);
 /// assert_eq!(max_value, 0x7); // Binary: 0111 (all permissions combined)
@@ -140,22 +367,65 @@ pub fn is_valid_hex(value: CapilityHexValue, descriptor: &CapabilityDescriptor)
 ///
 /// This function iterates through all values in the descriptor once, making it O(n)
 /// where n is the number of capabilities in the descriptor.
-pub fn get_max_hex_value_descriptor(descriptor: &CapabilityDescriptor) -> CapilityHexValue {
-    let mut max_value: CapilityHexValue = 0;
-    for &unit_value in descriptor.values() {
-        max_value |= unit_value;
+pub fn get_max_hex_value_descriptor<D: DescriptorLike>(descriptor: &D) -> D::Value {
+    let mut max_value = D::Value::zero();
+    for unit_value in descriptor.capability_map().values() {
+        max_value = max_value.or(unit_value);
     }
     max_value
 }
 
-pub fn get_sum_hex_value_descriptor(descriptor: &CapabilityDescriptor) -> CapilityHexValue {
-    let mut sum_value: CapilityHexValue = 0;
-    for &unit_value in descriptor.values() {
-        sum_value += unit_value;
+/// Calculates the arithmetic sum of all capability values in a descriptor.
+///
+/// For a well-formed descriptor (distinct power-of-2 values) the sum equals the
+/// value returned by [`get_max_hex_value_descriptor`]. A difference between the two
+/// indicates overlapping bits. The sum wraps around if it overflows the value type.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{models::CapabilityDescriptor, checks::get_sum_hex_value_descriptor};
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("Write", 0x2)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(get_sum_hex_value_descriptor(&descriptor), 0x3);
+/// ```
+pub fn get_sum_hex_value_descriptor<D: DescriptorLike>(descriptor: &D) -> D::Value {
+    let mut sum_value = D::Value::zero();
+    for unit_value in descriptor.capability_map().values() {
+        sum_value = sum_value.overflowing_add(unit_value).0;
     }
     sum_value
 }
 
+/// Calculates the arithmetic sum of all capability values, returning `None` on overflow.
+///
+/// This is the overflow-aware counterpart of [`get_sum_hex_value_descriptor`], which wraps
+/// around silently. An overflow means the descriptor cannot pass the integrity rule.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{models::CapabilityMap, checks::checked_sum_hex_value_descriptor};
+///
+/// let mut descriptor = CapabilityMap::new();
+/// descriptor.insert("Read".to_string(), 0x1);
+/// descriptor.insert("Huge".to_string(), i32::MAX);
+///
+/// assert_eq!(checked_sum_hex_value_descriptor(&descriptor), None);
+/// ```
+pub fn checked_sum_hex_value_descriptor<D: DescriptorLike>(descriptor: &D) -> Option<D::Value> {
+    let mut sum_value = D::Value::zero();
+    for unit_value in descriptor.capability_map().values() {
+        sum_value = sum_value.checked_add(unit_value)?;
+    }
+    Some(sum_value)
+}
+
 // WASM-compatible wrapper functions
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
@@ -185,10 +455,10 @@ pub fn js_get_sum_hex_value_descriptor(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::CapabilityDescriptor;
+    use crate::models::CapabilityMap;
 
-    fn create_test_descriptor() -> CapabilityDescriptor {
-        let mut descriptor = CapabilityDescriptor::new();
+    fn create_test_descriptor() -> CapabilityMap {
+        let mut descriptor = CapabilityMap::new();
         descriptor.insert("Read".to_string(), 0x1);
         descriptor.insert("Write".to_string(), 0x2);
         descriptor.insert("Execute".to_string(), 0x4);
@@ -219,7 +489,7 @@ mod tests {
 
     #[test]
     fn test_is_valid_hex_empty_descriptor() {
-        let descriptor = CapabilityDescriptor::new();
+        let descriptor = CapabilityMap::new();
 
         assert!(is_valid_hex(0x0, &descriptor)); // Empty is valid
         assert!(!is_valid_hex(0x1, &descriptor)); // Any bit is invalid
@@ -228,7 +498,7 @@ mod tests {
 
     #[test]
     fn test_is_valid_hex_single_permission() {
-        let mut descriptor = CapabilityDescriptor::new();
+        let mut descriptor = CapabilityMap::new();
         descriptor.insert("OnlyPermission".to_string(), 0x4);
 
         assert!(is_valid_hex(0x0, &descriptor)); // No permissions
@@ -243,10 +513,10 @@ mod tests {
         let descriptor = create_test_descriptor();
         assert_eq!(get_max_hex_value_descriptor(&descriptor), 0xF);
 
-        let empty_descriptor = CapabilityDescriptor::new();
+        let empty_descriptor: CapabilityMap = CapabilityMap::new();
         assert_eq!(get_max_hex_value_descriptor(&empty_descriptor), 0x0);
 
-        let mut single_descriptor = CapabilityDescriptor::new();
+        let mut single_descriptor = CapabilityMap::new();
         single_descriptor.insert("Single".to_string(), 0x8);
         assert_eq!(get_max_hex_value_descriptor(&single_descriptor), 0x8);
     }
@@ -257,11 +527,11 @@ mod tests {
         // Sum: 0x1 + 0x2 + 0x4 + 0x8 = 0xF
         assert_eq!(get_sum_hex_value_descriptor(&descriptor), 0xF);
 
-        let empty_descriptor = CapabilityDescriptor::new();
+        let empty_descriptor: CapabilityMap = CapabilityMap::new();
         assert_eq!(get_sum_hex_value_descriptor(&empty_descriptor), 0x0);
 
         // Test with overlapping bits to show difference between OR and sum
-        let mut overlapping_descriptor = CapabilityDescriptor::new();
+        let mut overlapping_descriptor = CapabilityMap::new();
         overlapping_descriptor.insert("Permission1".to_string(), 0x3); // Binary: 11
         overlapping_descriptor.insert("Permission2".to_string(), 0x1); // Binary: 01 (overlaps)
 
@@ -270,6 +540,17 @@ mod tests {
         assert_eq!(get_sum_hex_value_descriptor(&overlapping_descriptor), 0x4);
     }
 
+    #[test]
+    fn test_checked_sum_hex_value_descriptor() {
+        let descriptor = create_test_descriptor();
+        assert_eq!(checked_sum_hex_value_descriptor(&descriptor), Some(0xF));
+
+        let mut overflow_descriptor = CapabilityMap::new();
+        overflow_descriptor.insert("A".to_string(), 0x40000000);
+        overflow_descriptor.insert("B".to_string(), 0x40000000);
+        assert_eq!(checked_sum_hex_value_descriptor(&overflow_descriptor), None);
+    }
+
     #[test]
     fn test_descriptor_integrity_validation() {
         // Test normal case where OR equals sum (no overlapping bits)
@@ -282,7 +563,7 @@ mod tests {
 
     #[test]
     fn test_large_permission_system() {
-        let mut large_descriptor = CapabilityDescriptor::new();
+        let mut large_descriptor = CapabilityMap::new();
         for i in 0..10 {
             large_descriptor.insert(format!("Permission{}", i), 1 << i);
         }
@@ -304,7 +585,7 @@ mod tests {
     #[test]
     fn test_boundary_conditions() {
         // Test with maximum i32 values
-        let mut max_descriptor = CapabilityDescriptor::new();
+        let mut max_descriptor = CapabilityMap::new();
         max_descriptor.insert("MaxPermission".to_string(), 0x40000000); // Bit 30
 
         assert!(is_valid_hex(0x0, &max_descriptor));
@@ -312,7 +593,7 @@ mod tests {
         assert!(!is_valid_hex(0x80000000u32 as i32, &max_descriptor)); // This would be negative
 
         // Test edge case with negative numbers (though shouldn't happen in practice)
-        let mut negative_descriptor = CapabilityDescriptor::new();
+        let mut negative_descriptor = CapabilityMap::new();
         negative_descriptor.insert("NegativeTest".to_string(), -1);
 
         // This tests the robustness of our validation with unexpected inputs
@@ -327,7 +608,7 @@ mod tests {
         assert!(is_valid_hex(0x0, &descriptor));
 
         // Even with empty descriptor
-        let empty_descriptor = CapabilityDescriptor::new();
+        let empty_descriptor = CapabilityMap::new();
         assert!(is_valid_hex(0x0, &empty_descriptor));
     }
 
@@ -344,4 +625,109 @@ mod tests {
         assert!(!is_valid_hex(0x11, &descriptor)); // Has invalid bit 5
         assert!(!is_valid_hex(0x33, &descriptor)); // Has invalid bits 5 and 6
     }
+
+    #[test]
+    fn test_unsigned_value_types() {
+        let mut descriptor: CapabilityMap<u32> = CapabilityMap::new();
+        descriptor.insert("Low".to_string(), 0x1);
+        descriptor.insert("SignBit".to_string(), 0x80000000);
+
+        // The top bit is a regular capability for unsigned values
+        assert!(is_valid_hex(0x80000001, &descriptor));
+        assert!(!is_valid_hex(0x2, &descriptor));
+        assert_eq!(get_max_hex_value_descriptor(&descriptor), 0x80000001);
+    }
+
+    #[test]
+    fn test_sum_overflow_is_invalid() {
+        let mut descriptor: CapabilityMap<u64> = CapabilityMap::new();
+        descriptor.insert("All".to_string(), u64::MAX);
+        descriptor.insert("One".to_string(), 0x1);
+
+        // The sum wraps around, so the descriptor fails the integrity check
+        assert_eq!(get_sum_hex_value_descriptor(&descriptor), 0x0);
+        assert!(!is_valid_hex(0x1, &descriptor));
+    }
+
+    #[test]
+    fn test_validate_matches_is_valid_hex() {
+        let descriptor = create_test_descriptor();
+        for value in [0x0, 0x1, 0x7, 0xF, 0x10, 0x33, -1, i32::MIN] {
+            assert_eq!(
+                validate(&value, &descriptor).is_ok(),
+                is_valid_hex(value, &descriptor),
+                "Mismatch for 0x{:X}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_unknown_bits() {
+        let descriptor = create_test_descriptor();
+        let error = validate(&0x33, &descriptor).unwrap_err();
+
+        assert_eq!(error.rule(), ValidationRule::UnknownBits);
+        assert_eq!(error.bits(), &[4, 5]);
+    }
+
+    #[test]
+    fn test_validate_negative_value() {
+        let descriptor = create_test_descriptor();
+        let error = validate(&(0x80000000u32 as i32), &descriptor).unwrap_err();
+
+        assert_eq!(error, ValidationError::NegativeValue { bits: vec![31] });
+    }
+
+    #[test]
+    fn test_validate_corrupted_descriptor() {
+        let mut negative_descriptor = CapabilityMap::new();
+        negative_descriptor.insert("Valid".to_string(), 0x1);
+        negative_descriptor.insert("NegativeTest".to_string(), -1);
+
+        let error = validate(&0x1, &negative_descriptor).unwrap_err();
+        assert_eq!(error.rule(), ValidationRule::DescriptorIntegrity);
+        assert_eq!(error.bits(), &[0, 31]);
+
+        // Overflowing sum with overlapping bits
+        let mut overflow_descriptor: CapabilityMap<u64> = CapabilityMap::new();
+        overflow_descriptor.insert("All".to_string(), u64::MAX);
+        overflow_descriptor.insert("One".to_string(), 0x1);
+
+        let error = validate(&0x1, &overflow_descriptor).unwrap_err();
+        assert_eq!(
+            error,
+            ValidationError::DescriptorIntegrity { bits: vec![0] }
+        );
+    }
+
+    #[test]
+    fn test_validation_error_display() {
+        let error = ValidationError::NegativeValue { bits: vec![31] };
+        assert_eq!(error.to_string(), "negative value: sign bit 31 is set");
+
+        let error = ValidationError::DescriptorIntegrity { bits: vec![0, 1] };
+        assert_eq!(
+            error.to_string(),
+            "descriptor integrity: descriptor values overlap or overflow at bit(s) 0, 1"
+        );
+
+        // Usable as a boxed standard error
+        let boxed: Box<dyn std::error::Error> = Box::new(error);
+        assert!(boxed.to_string().starts_with("descriptor integrity"));
+    }
+
+    #[test]
+    fn test_checks_accept_validated_descriptor() {
+        let descriptor = crate::models::CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .build()
+            .unwrap();
+
+        assert!(is_valid_hex(0x3, &descriptor));
+        assert!(!is_valid_hex(0x4, &descriptor));
+        assert_eq!(get_max_hex_value_descriptor(&descriptor), 0x3);
+        assert_eq!(checked_sum_hex_value_descriptor(&descriptor), Some(0x3));
+    }
 }
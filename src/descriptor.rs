@@ -0,0 +1,1624 @@
+//! # Descriptor Module
+//!
+//! This module defines [`CapabilityDescriptor`], the validated and immutable mapping between
+//! capability names and their bit values, together with the [`CapabilityDescriptorBuilder`]
+//! used to create it.
+//!
+//! ## Guarantees
+//!
+//! A `CapabilityDescriptor` can only be obtained through its builder or through
+//! `TryFrom<CapabilityMap>`, both of which enforce that:
+//! - Every value is a single, non-negative bit
+//! - No two capabilities share a bit
+//! - Names are unique
+//!
+//! Once built, a descriptor cannot be modified.
+//!
+//! ## Level Fields
+//!
+//! A descriptor can also reserve a group of bits for a [`LevelField`], whose bit patterns
+//! name ordered levels instead of independent flags. See the [`level`](crate::level)
+//! module.
+//!
+//! ## Composite Capabilities
+//!
+//! Besides its single-bit capabilities, a descriptor can name combinations of them, such
+//! as `ReadWrite = 0x3`. Composites are looked up by [`CapabilityDescriptor::get`] but are
+//! not part of [`iter`](CapabilityDescriptor::iter) or [`len`](CapabilityDescriptor::len).
+//! A composite is held in full only if every one of its bits is set, which
+//! [`CapabilityMatch::AllBits`](crate::role_capability::CapabilityMatch::AllBits) checks:
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     role_capability::{CapabilityMatch, RoleCapability},
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Write", 0x2)
+//!     .composite("ReadWrite", 0x3)
+//!     .build()
+//!     .unwrap();
+//!
+//! let reader = RoleCapability::new(descriptor, 0x1);
+//! assert!(reader.has_capability(&"ReadWrite".to_string()));
+//! assert!(!reader.has_capability_with("ReadWrite", CapabilityMatch::AllBits));
+//! ```
+//!
+//! ## Bypass Capabilities
+//!
+//! A capability can be marked as a bypass that grants every capability of the descriptor,
+//! like an `Administrator` flag, or a named subset of them. Capability checks such as
+//! [`RoleCapability::has_capability`](crate::role_capability::RoleCapability::has_capability)
+//! honour bypasses, while the raw value and the name listings built from it keep only the
+//! bits that are actually set:
+//!
+//! ```rust
+//! use permission_translation::{models::CapabilityDescriptor, role_capability::RoleCapability};
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Administrator", 0x1)
+//!     .capability("Read", 0x2)
+//!     .capability("Write", 0x4)
+//!     .capability("Moderator", 0x8)
+//!     .grants_all("Administrator")
+//!     .grants("Moderator", ["Read"])
+//!     .build()
+//!     .unwrap();
+//!
+//! let admin = RoleCapability::new(descriptor.clone(), 0x1);
+//! assert!(admin.has_capability(&"Write".to_string()));
+//! assert_eq!(admin.granted_value(), 0xF);
+//!
+//! let moderator = RoleCapability::new(descriptor, 0x8);
+//! assert!(moderator.has_capability(&"Read".to_string()));
+//! assert!(!moderator.has_capability(&"Write".to_string()));
+//! ```
+//!
+//! ## Implications
+//!
+//! A capability can imply others, for example `Write` implies `Read`. Implications are
+//! transitive and must not form a cycle. Unlike bypasses, they are only applied on request,
+//! through [`RoleCapability::expand`](crate::role_capability::RoleCapability::expand) or
+//! the [`CapabilityMatch::Implied`](crate::role_capability::CapabilityMatch::Implied) mode:
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     role_capability::{CapabilityMatch, RoleCapability},
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("ViewChannel", 0x1)
+//!     .capability("SendMessages", 0x2)
+//!     .capability("ManageChannels", 0x4)
+//!     .implies("ManageChannels", ["SendMessages"])
+//!     .implies("SendMessages", ["ViewChannel"])
+//!     .build()
+//!     .unwrap();
+//!
+//! let manager = RoleCapability::new(descriptor, 0x4);
+//! assert!(!manager.has_capability(&"ViewChannel".to_string()));
+//! assert!(manager.has_capability_with("ViewChannel", CapabilityMatch::Implied));
+//! assert_eq!(manager.expand().hex_value, 0x7);
+//! ```
+//!
+//! ## Migrating from `HashMap`
+//!
+//! Code that builds descriptors as a [`CapabilityMap`] (a plain `HashMap`) can convert them
+//! with `TryFrom`:
+//!
+//! ```rust
+//! use permission_translation::models::{CapabilityDescriptor, CapabilityMap};
+//!
+//! let mut map = CapabilityMap::new();
+//! map.insert("Read".to_string(), 0x1);
+//! map.insert("Write".to_string(), 0x2);
+//!
+//! let descriptor = CapabilityDescriptor::try_from(map).unwrap();
+//! assert_eq!(descriptor.get("Write"), Some(&0x2));
+//! ```
+
+use std::collections::{btree_map, hash_map, BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::level::{split_level, LevelField};
+use crate::models::{CapabilityHexUnitValue, CapabilityMap, CapabilityName};
+use crate::namespace::{namespace_value, split_wildcard};
+use crate::value::PermissionValue;
+
+/// Read access to the name-to-value entries of a descriptor.
+///
+/// The functions in [`checks`](crate::checks) and [`lint`](crate::lint) accept any
+/// `DescriptorLike`, so they work both with a validated [`CapabilityDescriptor`] and with a raw
+/// [`CapabilityMap`] that may still contain corrupted entries.
+pub trait DescriptorLike {
+    /// The permission value type used by the descriptor.
+    type Value: PermissionValue;
+
+    /// Returns the capability entries.
+    fn capability_map(&self) -> &CapabilityMap<Self::Value>;
+
+    /// Returns the multi-bit level fields. A raw [`CapabilityMap`] has none.
+    fn level_fields(&self) -> &[LevelField<Self::Value>] {
+        &[]
+    }
+
+    /// Returns the value a name stands for: a capability, a `namespace.*` wildcard or a
+    /// `Field=level` of a level field. [`CapabilityDescriptor`] also resolves composites.
+    fn resolve(&self, name: &str) -> Option<Self::Value> {
+        let capabilities = self.capability_map();
+        if let Some(value) = capabilities.get(name) {
+            return Some(value.clone());
+        }
+        if let Some(namespace) = split_wildcard(name) {
+            return namespace_value(capabilities, namespace);
+        }
+        let (field, level) = split_level(name)?;
+        self.level_fields()
+            .iter()
+            .find(|candidate| candidate.name() == field)?
+            .encode(level)
+    }
+}
+
+impl<V: PermissionValue> DescriptorLike for CapabilityMap<V> {
+    type Value = V;
+
+    fn capability_map(&self) -> &CapabilityMap<V> {
+        self
+    }
+}
+
+/// A validated, immutable mapping between capability names and their bit values.
+///
+/// This descriptor defines the available capabilities in a permission system
+/// and their associated bit values. It serves as the authoritative source
+/// for validating and translating permission values.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::models::CapabilityDescriptor;
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Administrator", 0x1)
+///     .capability("ManageServer", 0x2)
+///     .next_capability("ManageRoles") // Assigned 0x4
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(descriptor.len(), 3);
+/// assert_eq!(descriptor.get("ManageRoles"), Some(&0x4));
+/// ```
+///
+/// The value type defaults to [`CapabilityHexUnitValue`] but can be any
+/// [`PermissionValue`], such as `u64` or `u128`:
+///
+/// ```rust
+/// use permission_translation::models::CapabilityDescriptor;
+///
+/// let descriptor = CapabilityDescriptor::<u64>::builder()
+///     .capability("SendPolls", 1 << 49)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityDescriptor<V = CapabilityHexUnitValue> {
+    entries: CapabilityMap<V>,
+    bypasses: BTreeMap<CapabilityName, Bypass>,
+    implications: BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    prerequisites: BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    exclusions: Vec<(CapabilityName, CapabilityName)>,
+    composites: BTreeMap<CapabilityName, V>,
+    fields: Vec<LevelField<V>>,
+}
+
+/// What a bypass capability grants to the roles that hold it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Bypass {
+    /// Every capability of the descriptor.
+    All,
+    /// The listed capabilities.
+    Capabilities(Vec<CapabilityName>),
+}
+
+impl<V: PermissionValue> CapabilityDescriptor<V> {
+    /// Returns a builder for a new descriptor.
+    pub fn builder() -> CapabilityDescriptorBuilder<V> {
+        CapabilityDescriptorBuilder::new()
+    }
+
+    /// Returns the value of the named capability or composite, if it exists.
+    pub fn get(&self, name: &str) -> Option<&V> {
+        self.entries.get(name).or_else(|| self.composites.get(name))
+    }
+
+    /// Returns `true` if the descriptor defines the named capability or composite.
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns the value a name stands for: a capability, a composite, a `namespace.*`
+    /// wildcard, or a `Field=level` of a level field.
+    pub fn resolve(&self, name: &str) -> Option<V> {
+        if let Some(value) = self.get(name) {
+            return Some(value.clone());
+        }
+        if let Some(namespace) = split_wildcard(name) {
+            return self.namespace(namespace);
+        }
+        let (field, level) = split_level(name)?;
+        self.level_field(field)?.encode(level)
+    }
+
+    /// Returns the value of the named composite, if it exists.
+    pub fn composite(&self, name: &str) -> Option<&V> {
+        self.composites.get(name)
+    }
+
+    /// Returns the named level field, if it exists.
+    pub fn level_field(&self, name: &str) -> Option<&LevelField<V>> {
+        self.fields.iter().find(|field| field.name() == name)
+    }
+
+    /// Returns the level fields, in the order they were added.
+    pub fn level_fields(&self) -> &[LevelField<V>] {
+        &self.fields
+    }
+
+    /// Iterates over the composites, ordered by name.
+    pub fn composites(&self) -> btree_map::Iter<'_, CapabilityName, V> {
+        self.composites.iter()
+    }
+
+    /// Returns the number of single-bit capabilities, not counting composites.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the descriptor defines no capabilities.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over `(name, value)` pairs in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, CapabilityName, V> {
+        self.entries.iter()
+    }
+
+    /// Iterates over the capability names in arbitrary order.
+    pub fn names(&self) -> hash_map::Keys<'_, CapabilityName, V> {
+        self.entries.keys()
+    }
+
+    /// Iterates over the capability values in arbitrary order.
+    pub fn values(&self) -> hash_map::Values<'_, CapabilityName, V> {
+        self.entries.values()
+    }
+
+    /// Returns the entries as a plain map.
+    pub fn as_map(&self) -> &CapabilityMap<V> {
+        &self.entries
+    }
+
+    /// Returns the `(name, value)` pairs ordered by bit position.
+    ///
+    /// Unlike [`iter`](CapabilityDescriptor::iter), the order is the same on every run.
+    /// Entries are ordered by their lowest bit, then by name.
+    pub fn sorted_entries(&self) -> Vec<(&CapabilityName, &V)> {
+        let mut entries: Vec<(Option<u32>, &CapabilityName, &V)> = self
+            .entries
+            .iter()
+            .map(|(name, value)| (value.bit_positions().first().copied(), name, value))
+            .collect();
+        entries.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        entries
+            .into_iter()
+            .map(|(_, name, value)| (name, value))
+            .collect()
+    }
+
+    /// Returns what the named capability grants as a bypass, if it is one.
+    pub fn bypass(&self, name: &str) -> Option<&Bypass> {
+        self.bypasses.get(name)
+    }
+
+    /// Iterates over the bypass capabilities, ordered by name.
+    pub fn bypasses(&self) -> btree_map::Iter<'_, CapabilityName, Bypass> {
+        self.bypasses.iter()
+    }
+
+    /// Returns the value granted by the named bypass capability, or `None` if the
+    /// capability is not a bypass.
+    ///
+    /// An "all" bypass grants every entry but no level field bits.
+    pub fn bypass_grants(&self, name: &str) -> Option<V> {
+        let granted = match self.bypasses.get(name)? {
+            Bypass::All => self
+                .entries
+                .values()
+                .fold(V::zero(), |mask, value| mask.or(value)),
+            Bypass::Capabilities(names) => names
+                .iter()
+                .filter_map(|granted| self.entries.get(granted))
+                .fold(V::zero(), |mask, value| mask.or(value)),
+        };
+        Some(granted)
+    }
+
+    /// Returns the capabilities granted by the bypass capabilities set in `value`.
+    ///
+    /// The result does not include `value` itself; OR the two to get everything a role
+    /// holding `value` is granted.
+    pub fn granted_by(&self, value: &V) -> V {
+        self.bypasses
+            .keys()
+            .filter(|name| {
+                self.entries
+                    .get(*name)
+                    .is_some_and(|bit| value.intersects(bit))
+            })
+            .filter_map(|name| self.bypass_grants(name))
+            .fold(V::zero(), |mask, granted| mask.or(&granted))
+    }
+
+    /// Returns the capabilities the named capability implies directly.
+    pub fn implied(&self, name: &str) -> &[CapabilityName] {
+        self.implications
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Iterates over the implication rules, ordered by the implying capability's name.
+    pub fn implications(&self) -> btree_map::Iter<'_, CapabilityName, Vec<CapabilityName>> {
+        self.implications.iter()
+    }
+
+    /// Returns `value` together with every capability it implies, directly or transitively.
+    pub fn implied_by(&self, value: &V) -> V {
+        let mut closure = value.clone();
+        let mut pending: Vec<&CapabilityName> = self
+            .implications
+            .keys()
+            .filter(|name| {
+                self.entries
+                    .get(*name)
+                    .is_some_and(|bit| value.intersects(bit))
+            })
+            .collect();
+        while let Some(name) = pending.pop() {
+            for implied in self.implied(name) {
+                let bit = &self.entries[implied];
+                if !closure.intersects(bit) {
+                    closure = closure.or(bit);
+                    pending.push(implied);
+                }
+            }
+        }
+        closure
+    }
+
+    /// Returns the prerequisites of the named capability.
+    pub fn required(&self, name: &str) -> &[CapabilityName] {
+        self.prerequisites
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Iterates over the prerequisite rules, ordered by the dependent capability's name.
+    pub fn prerequisites(&self) -> btree_map::Iter<'_, CapabilityName, Vec<CapabilityName>> {
+        self.prerequisites.iter()
+    }
+
+    /// Returns the mutually exclusive pairs, in declaration order.
+    pub fn exclusions(&self) -> &[(CapabilityName, CapabilityName)] {
+        &self.exclusions
+    }
+}
+
+impl<V: PermissionValue> Default for CapabilityDescriptor<V> {
+    fn default() -> Self {
+        CapabilityDescriptor {
+            entries: CapabilityMap::new(),
+            bypasses: BTreeMap::new(),
+            implications: BTreeMap::new(),
+            prerequisites: BTreeMap::new(),
+            exclusions: Vec::new(),
+            composites: BTreeMap::new(),
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl<V: PermissionValue> DescriptorLike for CapabilityDescriptor<V> {
+    type Value = V;
+
+    fn capability_map(&self) -> &CapabilityMap<V> {
+        &self.entries
+    }
+
+    fn level_fields(&self) -> &[LevelField<V>] {
+        &self.fields
+    }
+
+    fn resolve(&self, name: &str) -> Option<V> {
+        CapabilityDescriptor::resolve(self, name)
+    }
+}
+
+impl<'a, V> IntoIterator for &'a CapabilityDescriptor<V> {
+    type Item = (&'a CapabilityName, &'a V);
+    type IntoIter = hash_map::Iter<'a, CapabilityName, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl<V: PermissionValue> TryFrom<CapabilityMap<V>> for CapabilityDescriptor<V> {
+    type Error = DescriptorError;
+
+    /// Validates a plain map. Entries are checked in name order, so the reported
+    /// error is deterministic.
+    fn try_from(map: CapabilityMap<V>) -> Result<Self, Self::Error> {
+        builder_from_map(map).build()
+    }
+}
+
+/// Returns a builder holding the entries of a plain map, added in name order.
+fn builder_from_map<V: PermissionValue>(map: CapabilityMap<V>) -> CapabilityDescriptorBuilder<V> {
+    let mut entries: Vec<(CapabilityName, V)> = map.into_iter().collect();
+    entries.sort();
+    entries
+        .into_iter()
+        .fold(CapabilityDescriptor::builder(), |builder, (name, value)| {
+            builder.capability(name, value)
+        })
+}
+
+/// Serializes a descriptor that only has capabilities as a map from name to value, in bit
+/// order. A descriptor with level fields, composites or rules serializes as a document
+/// with a `capabilities` map and, where not empty, `level_fields`, `composites`,
+/// `bypasses`, `implies`, `requires` and `exclusive`.
+#[cfg(feature = "serde")]
+impl<V: PermissionValue + serde::Serialize> serde::Serialize for CapabilityDescriptor<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let sections = [
+            !self.fields.is_empty(),
+            !self.composites.is_empty(),
+            !self.bypasses.is_empty(),
+            !self.implications.is_empty(),
+            !self.prerequisites.is_empty(),
+            !self.exclusions.is_empty(),
+        ];
+        if !sections.contains(&true) {
+            return BitOrderedEntries(self).serialize(serializer);
+        }
+
+        let count = 1 + sections.iter().filter(|present| **present).count();
+        let mut document = serializer.serialize_struct("CapabilityDescriptor", count)?;
+        document.serialize_field("capabilities", &BitOrderedEntries(self))?;
+        if !self.fields.is_empty() {
+            document.serialize_field("level_fields", &self.fields)?;
+        }
+        if !self.composites.is_empty() {
+            document.serialize_field("composites", &self.composites)?;
+        }
+        if !self.bypasses.is_empty() {
+            document.serialize_field("bypasses", &self.bypasses)?;
+        }
+        if !self.implications.is_empty() {
+            document.serialize_field("implies", &self.implications)?;
+        }
+        if !self.prerequisites.is_empty() {
+            document.serialize_field("requires", &self.prerequisites)?;
+        }
+        if !self.exclusions.is_empty() {
+            document.serialize_field("exclusive", &self.exclusions)?;
+        }
+        document.end()
+    }
+}
+
+/// The capabilities of a descriptor, serialized as a map in bit order.
+#[cfg(feature = "serde")]
+struct BitOrderedEntries<'a, V>(&'a CapabilityDescriptor<V>);
+
+#[cfg(feature = "serde")]
+impl<V: PermissionValue + serde::Serialize> serde::Serialize for BitOrderedEntries<'_, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let entries = self.0.sorted_entries();
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (name, value) in entries {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes either form written by `Serialize` and rebuilds the descriptor through
+/// the builder, with the same checks.
+#[cfg(feature = "serde")]
+impl<'de, V: PermissionValue + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for CapabilityDescriptor<V>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let descriptor = match SerializedDescriptor::<V>::deserialize(deserializer)? {
+            SerializedDescriptor::Document(document) => document.build(),
+            SerializedDescriptor::Map(map) => CapabilityDescriptor::try_from(map),
+        };
+        descriptor.map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(
+    untagged,
+    expecting = "a map of capability values or a descriptor with a `capabilities` map"
+)]
+enum SerializedDescriptor<V> {
+    Document(DescriptorDocument<V>),
+    Map(CapabilityMap<V>),
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields, bound(deserialize = "V: serde::Deserialize<'de>"))]
+struct DescriptorDocument<V> {
+    capabilities: CapabilityMap<V>,
+    #[serde(default)]
+    level_fields: Vec<LevelFieldDocument<V>>,
+    #[serde(default)]
+    composites: BTreeMap<CapabilityName, V>,
+    #[serde(default)]
+    bypasses: BTreeMap<CapabilityName, Bypass>,
+    #[serde(default)]
+    implies: BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    #[serde(default)]
+    requires: BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    #[serde(default)]
+    exclusive: Vec<(CapabilityName, CapabilityName)>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LevelFieldDocument<V> {
+    name: CapabilityName,
+    mask: V,
+    levels: Vec<CapabilityName>,
+}
+
+#[cfg(feature = "serde")]
+impl<V: PermissionValue> DescriptorDocument<V> {
+    fn build(self) -> Result<CapabilityDescriptor<V>, DescriptorError> {
+        let mut builder = builder_from_map(self.capabilities);
+        for field in self.level_fields {
+            builder = builder.level_field(field.name, field.mask, field.levels);
+        }
+        for (name, value) in self.composites {
+            builder = builder.composite(name, value);
+        }
+        for (name, bypass) in self.bypasses {
+            builder = match bypass {
+                Bypass::All => builder.grants_all(name),
+                Bypass::Capabilities(granted) => builder.grants(name, granted),
+            };
+        }
+        for (name, implied) in self.implies {
+            builder = builder.implies(name, implied);
+        }
+        for (name, required) in self.requires {
+            builder = builder.requires(name, required);
+        }
+        for (first, second) in self.exclusive {
+            builder = builder.exclusive(first, second);
+        }
+        builder.build()
+    }
+}
+
+/// Error returned when a descriptor entry is rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DescriptorError {
+    /// The same name was added twice.
+    DuplicateName { name: CapabilityName },
+    /// The value is zero and could never be granted.
+    ZeroValue { name: CapabilityName },
+    /// The value is negative.
+    NegativeValue { name: CapabilityName },
+    /// The value sets more than one bit. `bits` lists them.
+    NotSingleBit {
+        name: CapabilityName,
+        bits: Vec<u32>,
+    },
+    /// The bit is already used by another capability.
+    BitInUse {
+        name: CapabilityName,
+        existing: CapabilityName,
+        bit: u32,
+    },
+    /// No free bit is left in the value type for an auto-assigned capability.
+    NoFreeBit { name: CapabilityName },
+    /// A rule, such as a bypass, refers to a capability that is not defined.
+    UndefinedCapability { name: CapabilityName },
+    /// A level field declares fewer than two levels, or more than its bits can hold.
+    InvalidLevelCount {
+        name: CapabilityName,
+        count: usize,
+        bits: u32,
+    },
+    /// A composite sets bits that no single-bit capability defines. `bits` lists them.
+    UndefinedBits {
+        name: CapabilityName,
+        bits: Vec<u32>,
+    },
+    /// Implication rules form a cycle. The first name is repeated at the end.
+    ImplicationCycle { cycle: Vec<CapabilityName> },
+}
+
+impl fmt::Display for DescriptorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DescriptorError::DuplicateName { name } => {
+                write!(f, "capability {:?} is defined more than once", name)
+            }
+            DescriptorError::ZeroValue { name } => {
+                write!(f, "capability {:?} has the value 0", name)
+            }
+            DescriptorError::NegativeValue { name } => {
+                write!(f, "capability {:?} has a negative value", name)
+            }
+            DescriptorError::NotSingleBit { name, bits } => write!(
+                f,
+                "capability {:?} must be a single bit but sets bits {:?}",
+                name, bits
+            ),
+            DescriptorError::BitInUse {
+                name,
+                existing,
+                bit,
+            } => write!(
+                f,
+                "capability {:?} uses bit {} which is already assigned to {:?}",
+                name, bit, existing
+            ),
+            DescriptorError::NoFreeBit { name } => {
+                write!(f, "no free bit left to assign to capability {:?}", name)
+            }
+            DescriptorError::UndefinedCapability { name } => {
+                write!(f, "a rule refers to undefined capability {:?}", name)
+            }
+            DescriptorError::InvalidLevelCount { name, count, bits } => write!(
+                f,
+                "level field {:?} declares {} levels, but {} bit(s) hold between 2 and {}",
+                name,
+                count,
+                bits,
+                1u128.checked_shl(*bits).unwrap_or(u128::MAX)
+            ),
+            DescriptorError::UndefinedBits { name, bits } => write!(
+                f,
+                "composite {:?} sets bits {:?} that no capability defines",
+                name, bits
+            ),
+            DescriptorError::ImplicationCycle { cycle } => {
+                write!(f, "implications form a cycle: {}", cycle.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DescriptorError {}
+
+/// Builder for [`CapabilityDescriptor`].
+///
+/// Entries are checked as they are added; the first rejected entry is reported by
+/// [`build`](CapabilityDescriptorBuilder::build) and later entries are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::models::{CapabilityDescriptor, DescriptorError};
+///
+/// let error = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("View", 0x1)
+///     .build()
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     error,
+///     DescriptorError::BitInUse {
+///         name: "View".to_string(),
+///         existing: "Read".to_string(),
+///         bit: 0,
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CapabilityDescriptorBuilder<V = CapabilityHexUnitValue> {
+    entries: CapabilityMap<V>,
+    used: V,
+    bypasses: BTreeMap<CapabilityName, Bypass>,
+    implications: BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    prerequisites: BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    exclusions: Vec<(CapabilityName, CapabilityName)>,
+    composites: Vec<(CapabilityName, V)>,
+    fields: Vec<LevelField<V>>,
+    error: Option<DescriptorError>,
+}
+
+impl<V: PermissionValue> Default for CapabilityDescriptorBuilder<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: PermissionValue> CapabilityDescriptorBuilder<V> {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        CapabilityDescriptorBuilder {
+            entries: CapabilityMap::new(),
+            used: V::zero(),
+            bypasses: BTreeMap::new(),
+            implications: BTreeMap::new(),
+            prerequisites: BTreeMap::new(),
+            exclusions: Vec::new(),
+            composites: Vec::new(),
+            fields: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Adds a capability with an explicit single-bit value.
+    ///
+    /// The entry is rejected if the name already exists, or if the value is zero,
+    /// negative, has more than one bit, or reuses a bit from another capability.
+    pub fn capability(mut self, name: impl Into<CapabilityName>, value: V) -> Self {
+        if self.error.is_none() {
+            let name = name.into();
+            match self.check_entry(&name, &value) {
+                Ok(()) => {
+                    self.used = self.used.or(&value);
+                    self.entries.insert(name, value);
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+        self
+    }
+
+    /// Adds a capability on the lowest bit not used by any capability or level field, so
+    /// gaps left by explicit values are filled first.
+    pub fn next_capability(self, name: impl Into<CapabilityName>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let name = name.into();
+        // Stay clear of the sign bit so the assigned value is never negative
+        let next = (0..)
+            .map_while(V::bit)
+            .find(|bit| !self.used.intersects(bit))
+            .filter(|value| !value.is_negative());
+        match next {
+            Some(value) => self.capability(name, value),
+            None => CapabilityDescriptorBuilder {
+                error: Some(DescriptorError::NoFreeBit { name }),
+                ..self
+            },
+        }
+    }
+
+    /// Adds a multi-bit field whose bit patterns name levels, lowest first.
+    ///
+    /// Level `n` is stored as the number `n` spread over the bits of `mask`, from the lowest
+    /// bit up; the first level is usually a "none" level stored as zero. The field is
+    /// rejected if its name already exists, if the mask is zero, negative or reuses a bit,
+    /// if a level name repeats, or if there are fewer than two levels or more than the mask
+    /// can hold.
+    pub fn level_field<I, S>(mut self, name: impl Into<CapabilityName>, mask: V, levels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<CapabilityName>,
+    {
+        if self.error.is_none() {
+            let name = name.into();
+            let levels: Vec<CapabilityName> = levels.into_iter().map(Into::into).collect();
+            match self.check_field(&name, &mask, &levels) {
+                Ok(()) => {
+                    self.used = self.used.or(&mask);
+                    self.fields.push(LevelField::new(name, mask, levels));
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+        self
+    }
+
+    /// Adds a composite: a name for a combination of single-bit capabilities.
+    ///
+    /// The composite may be added before or after its capabilities. When the descriptor is
+    /// built, it is rejected if its name is already used, if its value is zero or
+    /// negative, or if it sets a bit that no capability defines.
+    pub fn composite(mut self, name: impl Into<CapabilityName>, value: V) -> Self {
+        self.composites.push((name.into(), value));
+        self
+    }
+
+    /// Marks a capability as a bypass that grants every capability of the descriptor.
+    ///
+    /// Level fields are not granted: a field holds exactly one level, so a role keeps the
+    /// level it actually has. Give the role the level it needs alongside the bypass.
+    ///
+    /// The capability may be added before or after this call; it must exist when the
+    /// descriptor is built. A later bypass for the same capability replaces this one.
+    pub fn grants_all(mut self, name: impl Into<CapabilityName>) -> Self {
+        self.bypasses.insert(name.into(), Bypass::All);
+        self
+    }
+
+    /// Marks a capability as a bypass that grants the listed capabilities.
+    ///
+    /// Every name must exist when the descriptor is built. A later bypass for the same
+    /// capability replaces this one.
+    pub fn grants<I, S>(mut self, name: impl Into<CapabilityName>, granted: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<CapabilityName>,
+    {
+        let granted = granted.into_iter().map(Into::into).collect();
+        self.bypasses
+            .insert(name.into(), Bypass::Capabilities(granted));
+        self
+    }
+
+    /// Declares that holding a capability implies holding the listed capabilities.
+    ///
+    /// Calling this again for the same capability adds to its implications. Every name
+    /// must exist when the descriptor is built, and implications must not form a cycle.
+    pub fn implies<I, S>(mut self, name: impl Into<CapabilityName>, implied: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<CapabilityName>,
+    {
+        add_rule(&mut self.implications, name.into(), implied);
+        self
+    }
+
+    /// Declares that a capability is only useful together with the listed capabilities.
+    ///
+    /// Prerequisites are not enforced on values; see
+    /// [`RoleCapability::normalize`](crate::role_capability::RoleCapability::normalize).
+    /// Calling this again for the same capability adds to its prerequisites. Every name
+    /// must exist when the descriptor is built.
+    pub fn requires<I, S>(mut self, name: impl Into<CapabilityName>, required: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<CapabilityName>,
+    {
+        add_rule(&mut self.prerequisites, name.into(), required);
+        self
+    }
+
+    /// Declares that no role may hold both capabilities.
+    ///
+    /// Exclusions are not enforced on values; see
+    /// [`RoleCapability::exclusivity_violations`](crate::role_capability::RoleCapability::exclusivity_violations).
+    /// Declaring the same pair twice, in either order, has no further effect. Both names
+    /// must exist when the descriptor is built.
+    pub fn exclusive(
+        mut self,
+        first: impl Into<CapabilityName>,
+        second: impl Into<CapabilityName>,
+    ) -> Self {
+        let (first, second) = (first.into(), second.into());
+        let declared = self
+            .exclusions
+            .iter()
+            .any(|(a, b)| (a == &first && b == &second) || (a == &second && b == &first));
+        if !declared {
+            self.exclusions.push((first, second));
+        }
+        self
+    }
+
+    /// Finishes the descriptor, or returns the first rejected entry.
+    ///
+    /// Composites are checked after every entry, in the order they were added. Rules such
+    /// as bypasses, implications and prerequisites are checked next, in name order, and
+    /// exclusions last, in declaration order.
+    pub fn build(self) -> Result<CapabilityDescriptor<V>, DescriptorError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let mut composites = BTreeMap::new();
+        for (name, value) in &self.composites {
+            self.check_composite(name, value, &composites)?;
+            composites.insert(name.clone(), value.clone());
+        }
+        for (name, bypass) in &self.bypasses {
+            let granted = match bypass {
+                Bypass::All => &[][..],
+                Bypass::Capabilities(names) => names.as_slice(),
+            };
+            self.check_defined(std::iter::once(name).chain(granted))?;
+        }
+        for (name, targets) in self.implications.iter().chain(&self.prerequisites) {
+            self.check_defined(std::iter::once(name).chain(targets))?;
+        }
+        for (first, second) in &self.exclusions {
+            self.check_defined([first, second])?;
+        }
+        if let Some(cycle) = find_cycle(&self.implications) {
+            return Err(DescriptorError::ImplicationCycle { cycle });
+        }
+        Ok(CapabilityDescriptor {
+            entries: self.entries,
+            bypasses: self.bypasses,
+            implications: self.implications,
+            prerequisites: self.prerequisites,
+            exclusions: self.exclusions,
+            composites,
+            fields: self.fields,
+        })
+    }
+
+    fn check_defined<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a CapabilityName>,
+    ) -> Result<(), DescriptorError> {
+        match names
+            .into_iter()
+            .find(|name| !self.entries.contains_key(*name))
+        {
+            Some(name) => Err(DescriptorError::UndefinedCapability { name: name.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    fn check_composite(
+        &self,
+        name: &CapabilityName,
+        value: &V,
+        composites: &BTreeMap<CapabilityName, V>,
+    ) -> Result<(), DescriptorError> {
+        let name = name.clone();
+        if self.is_name_taken(&name) || composites.contains_key(&name) {
+            return Err(DescriptorError::DuplicateName { name });
+        }
+        if value.is_zero() {
+            return Err(DescriptorError::ZeroValue { name });
+        }
+        if value.is_negative() {
+            return Err(DescriptorError::NegativeValue { name });
+        }
+        let flags = self
+            .entries
+            .values()
+            .fold(V::zero(), |mask, flag| mask.or(flag));
+        let undefined = value.and_not(&flags);
+        if !undefined.is_zero() {
+            return Err(DescriptorError::UndefinedBits {
+                name,
+                bits: undefined.bit_positions(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_field(
+        &self,
+        name: &CapabilityName,
+        mask: &V,
+        levels: &[CapabilityName],
+    ) -> Result<(), DescriptorError> {
+        let name = name.clone();
+        if self.is_name_taken(&name) {
+            return Err(DescriptorError::DuplicateName { name });
+        }
+        if mask.is_zero() {
+            return Err(DescriptorError::ZeroValue { name });
+        }
+        if mask.is_negative() {
+            return Err(DescriptorError::NegativeValue { name });
+        }
+        self.check_bits_free(&name, mask)?;
+        if let Some(level) = levels
+            .iter()
+            .enumerate()
+            .find(|(index, level)| levels[..*index].contains(level))
+            .map(|(_, level)| level)
+        {
+            return Err(DescriptorError::DuplicateName {
+                name: format!("{}={}", name, level),
+            });
+        }
+        let bits = mask.count_ones();
+        let capacity = 1usize.checked_shl(bits).unwrap_or(usize::MAX);
+        if levels.len() < 2 || levels.len() > capacity {
+            return Err(DescriptorError::InvalidLevelCount {
+                name,
+                count: levels.len(),
+                bits,
+            });
+        }
+        Ok(())
+    }
+
+    fn is_name_taken(&self, name: &str) -> bool {
+        self.entries.contains_key(name) || self.fields.iter().any(|field| field.name() == name)
+    }
+
+    fn check_bits_free(&self, name: &CapabilityName, value: &V) -> Result<(), DescriptorError> {
+        let shared = self.used.and(value);
+        if shared.is_zero() {
+            return Ok(());
+        }
+        let existing = self
+            .entries
+            .iter()
+            .find(|(_, other)| other.intersects(&shared))
+            .map(|(other_name, _)| other_name)
+            .or_else(|| {
+                self.fields
+                    .iter()
+                    .find(|field| field.mask().intersects(&shared))
+                    .map(LevelField::name)
+            })
+            .cloned()
+            .unwrap_or_default();
+        Err(DescriptorError::BitInUse {
+            name: name.clone(),
+            existing,
+            bit: shared.bit_positions()[0],
+        })
+    }
+
+    fn check_entry(&self, name: &CapabilityName, value: &V) -> Result<(), DescriptorError> {
+        let name = name.clone();
+        if self.is_name_taken(&name) {
+            return Err(DescriptorError::DuplicateName { name });
+        }
+        if value.is_zero() {
+            return Err(DescriptorError::ZeroValue { name });
+        }
+        if value.is_negative() {
+            return Err(DescriptorError::NegativeValue { name });
+        }
+        if value.count_ones() != 1 {
+            return Err(DescriptorError::NotSingleBit {
+                name,
+                bits: value.bit_positions(),
+            });
+        }
+        self.check_bits_free(&name, value)
+    }
+}
+
+/// Adds targets to a name-to-names rule, skipping targets it already has.
+fn add_rule<I, S>(
+    rules: &mut BTreeMap<CapabilityName, Vec<CapabilityName>>,
+    name: CapabilityName,
+    targets: I,
+) where
+    I: IntoIterator<Item = S>,
+    S: Into<CapabilityName>,
+{
+    let existing = rules.entry(name).or_default();
+    for target in targets {
+        let target = target.into();
+        if !existing.contains(&target) {
+            existing.push(target);
+        }
+    }
+}
+
+/// Returns the first cycle found by a depth-first search of the rules, visiting names in
+/// order. The first name of the cycle is repeated at its end.
+fn find_cycle(
+    rules: &BTreeMap<CapabilityName, Vec<CapabilityName>>,
+) -> Option<Vec<CapabilityName>> {
+    fn visit<'a>(
+        name: &'a CapabilityName,
+        rules: &'a BTreeMap<CapabilityName, Vec<CapabilityName>>,
+        path: &mut Vec<&'a CapabilityName>,
+        done: &mut BTreeSet<&'a CapabilityName>,
+    ) -> Option<Vec<CapabilityName>> {
+        if let Some(start) = path.iter().position(|visiting| *visiting == name) {
+            let mut cycle: Vec<CapabilityName> =
+                path[start..].iter().map(|n| (*n).clone()).collect();
+            cycle.push(name.clone());
+            return Some(cycle);
+        }
+        if done.contains(name) {
+            return None;
+        }
+        path.push(name);
+        for next in rules.get(name).into_iter().flatten() {
+            if let Some(cycle) = visit(next, rules, path, done) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        done.insert(name);
+        None
+    }
+
+    let mut done = BTreeSet::new();
+    rules
+        .keys()
+        .find_map(|name| visit(name, rules, &mut Vec::new(), &mut done))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_with_explicit_values() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Admin", 0x80)
+            .build()
+            .unwrap();
+
+        assert_eq!(descriptor.len(), 3);
+        assert!(descriptor.contains("Admin"));
+        assert_eq!(descriptor.get("Write"), Some(&0x2));
+        assert_eq!(descriptor.get("Missing"), None);
+    }
+
+    #[test]
+    fn test_builder_auto_assigns_next_bit() {
+        let descriptor = CapabilityDescriptor::builder()
+            .next_capability("Read")
+            .next_capability("Write")
+            .capability("Admin", 0x10)
+            .level_field("Access", 0x24, ["none", "read"])
+            .next_capability("Owner")
+            .next_capability("Guest")
+            .next_capability("Bot")
+            .build()
+            .unwrap();
+
+        assert_eq!(descriptor.get("Read"), Some(&0x1));
+        assert_eq!(descriptor.get("Write"), Some(&0x2));
+        // Gaps below the highest used bit are filled first, skipping level field bits
+        assert_eq!(descriptor.get("Owner"), Some(&0x8));
+        assert_eq!(descriptor.get("Guest"), Some(&0x40));
+        assert_eq!(descriptor.get("Bot"), Some(&0x80));
+    }
+
+    #[test]
+    fn test_sorted_entries() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Zeta", 0x1)
+            .capability("Alpha", 0x8)
+            .capability("Mid", 0x2)
+            .build()
+            .unwrap();
+
+        let names: Vec<&str> = descriptor
+            .sorted_entries()
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Zeta", "Mid", "Alpha"]);
+    }
+
+    #[test]
+    fn test_builder_runs_out_of_bits() {
+        let mut builder = CapabilityDescriptor::<i32>::builder();
+        for i in 0..31 {
+            builder = builder.next_capability(format!("Permission{}", i));
+        }
+        let error = builder.next_capability("OneTooMany").build().unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::NoFreeBit {
+                name: "OneTooMany".to_string()
+            }
+        );
+
+        // Unsigned types can use their top bit
+        let mut builder = CapabilityDescriptor::<u32>::builder();
+        for i in 0..32 {
+            builder = builder.next_capability(format!("Permission{}", i));
+        }
+        assert_eq!(builder.build().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_entries() {
+        let cases: Vec<(CapabilityDescriptorBuilder, DescriptorError)> = vec![
+            (
+                CapabilityDescriptor::builder()
+                    .capability("Read", 0x1)
+                    .capability("Read", 0x2),
+                DescriptorError::DuplicateName {
+                    name: "Read".to_string(),
+                },
+            ),
+            (
+                CapabilityDescriptor::builder().capability("Nothing", 0x0),
+                DescriptorError::ZeroValue {
+                    name: "Nothing".to_string(),
+                },
+            ),
+            (
+                CapabilityDescriptor::builder().capability("Negative", i32::MIN),
+                DescriptorError::NegativeValue {
+                    name: "Negative".to_string(),
+                },
+            ),
+            (
+                CapabilityDescriptor::builder().capability("ReadWrite", 0x3),
+                DescriptorError::NotSingleBit {
+                    name: "ReadWrite".to_string(),
+                    bits: vec![0, 1],
+                },
+            ),
+        ];
+
+        for (builder, expected) in cases {
+            assert_eq!(builder.build().unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn test_first_error_is_kept() {
+        let error = CapabilityDescriptor::builder()
+            .capability("Zero", 0x0)
+            .capability("Negative", -1)
+            .capability("Valid", 0x1)
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            DescriptorError::ZeroValue {
+                name: "Zero".to_string()
+            }
+        );
+        assert_eq!(error.to_string(), "capability \"Zero\" has the value 0");
+    }
+
+    #[test]
+    fn test_try_from_map() {
+        let mut map = CapabilityMap::new();
+        map.insert("Read".to_string(), 0x1);
+        map.insert("Write".to_string(), 0x2);
+
+        let descriptor = CapabilityDescriptor::try_from(map.clone()).unwrap();
+        assert_eq!(descriptor.as_map(), &map);
+
+        map.insert("View".to_string(), 0x1);
+        let error = CapabilityDescriptor::try_from(map).unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::BitInUse {
+                name: "View".to_string(),
+                existing: "Read".to_string(),
+                bit: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_bypass_grants() {
+        let descriptor = CapabilityDescriptor::builder()
+            .grants_all("Administrator")
+            .capability("Administrator", 0x1)
+            .capability("Read", 0x2)
+            .capability("Write", 0x4)
+            .capability("Moderator", 0x8)
+            .grants("Moderator", ["Read", "Write"])
+            .build()
+            .unwrap();
+
+        assert_eq!(descriptor.bypass("Administrator"), Some(&Bypass::All));
+        assert_eq!(descriptor.bypass("Read"), None);
+        assert_eq!(descriptor.bypass_grants("Administrator"), Some(0xF));
+        assert_eq!(descriptor.bypass_grants("Moderator"), Some(0x6));
+        assert_eq!(descriptor.bypass_grants("Read"), None);
+        assert_eq!(descriptor.bypasses().count(), 2);
+
+        assert_eq!(descriptor.granted_by(&0x8), 0x6);
+        assert_eq!(descriptor.granted_by(&0x9), 0xF);
+        assert_eq!(descriptor.granted_by(&0x6), 0x0);
+    }
+
+    #[test]
+    fn test_bypass_all_excludes_level_fields() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Administrator", 0x1)
+            .capability("Read", 0x2)
+            .level_field("Support", 0xC, ["none", "agent", "lead"])
+            .grants_all("Administrator")
+            .build()
+            .unwrap();
+
+        assert_eq!(descriptor.bypass_grants("Administrator"), Some(0x3));
+
+        assert_eq!(descriptor.granted_by(&0x1), 0x3);
+        // A held level is kept, not raised
+        assert_eq!(descriptor.granted_by(&0x5), 0x3);
+        let support = descriptor.level_field("Support").unwrap();
+        assert_eq!(
+            support.decode(&(0x5 | descriptor.granted_by(&0x5))),
+            Some(&"agent".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bypass_must_refer_to_defined_capabilities() {
+        let error = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .grants_all("Administrator")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::UndefinedCapability {
+                name: "Administrator".to_string()
+            }
+        );
+
+        let error = CapabilityDescriptor::builder()
+            .capability("Moderator", 0x1)
+            .grants("Moderator", ["Kick"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "a rule refers to undefined capability \"Kick\""
+        );
+    }
+
+    #[test]
+    fn test_implications() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .capability("Read", 0x2)
+            .capability("Write", 0x4)
+            .capability("Manage", 0x8)
+            .capability("Audit", 0x10)
+            .implies("Write", ["Read"])
+            .implies("Read", ["View"])
+            .implies("Manage", ["Write"])
+            .implies("Manage", ["Audit", "Write"])
+            .build()
+            .unwrap();
+
+        assert_eq!(descriptor.implied("Manage"), &["Write", "Audit"]);
+        assert!(descriptor.implied("View").is_empty());
+        assert_eq!(descriptor.implications().count(), 3);
+
+        assert_eq!(descriptor.implied_by(&0x4), 0x7);
+        assert_eq!(descriptor.implied_by(&0x8), 0x1F);
+        assert_eq!(descriptor.implied_by(&0x1), 0x1);
+        assert_eq!(descriptor.implied_by(&0x0), 0x0);
+    }
+
+    #[test]
+    fn test_implication_cycles_are_rejected() {
+        let error = CapabilityDescriptor::builder()
+            .capability("A", 0x1)
+            .capability("B", 0x2)
+            .capability("C", 0x4)
+            .implies("A", ["B"])
+            .implies("B", ["C"])
+            .implies("C", ["A"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::ImplicationCycle {
+                cycle: vec!["A".into(), "B".into(), "C".into(), "A".into()],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "implications form a cycle: A -> B -> C -> A"
+        );
+
+        let error = CapabilityDescriptor::builder()
+            .capability("A", 0x1)
+            .implies("A", ["A"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::ImplicationCycle {
+                cycle: vec!["A".into(), "A".into()],
+            }
+        );
+
+        // A shared target is not a cycle
+        assert!(CapabilityDescriptor::builder()
+            .capability("A", 0x1)
+            .capability("B", 0x2)
+            .capability("C", 0x4)
+            .implies("A", ["B", "C"])
+            .implies("B", ["C"])
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_implications_must_refer_to_defined_capabilities() {
+        let error = CapabilityDescriptor::builder()
+            .capability("Write", 0x1)
+            .implies("Write", ["Read"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::UndefinedCapability {
+                name: "Read".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_prerequisites() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("ViewChannel", 0x1)
+            .capability("SendMessages", 0x2)
+            .capability("AttachFiles", 0x4)
+            .requires("SendMessages", ["ViewChannel"])
+            .requires("AttachFiles", ["SendMessages"])
+            .requires("AttachFiles", ["ViewChannel", "SendMessages"])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            descriptor.required("AttachFiles"),
+            &["SendMessages", "ViewChannel"]
+        );
+        assert!(descriptor.required("ViewChannel").is_empty());
+        assert_eq!(descriptor.prerequisites().count(), 2);
+
+        let error = CapabilityDescriptor::builder()
+            .capability("SendMessages", 0x1)
+            .requires("SendMessages", ["ViewChannel"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::UndefinedCapability {
+                name: "ViewChannel".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_exclusions() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("CreatePayment", 0x1)
+            .capability("ApprovePayment", 0x2)
+            .capability("Audit", 0x4)
+            .exclusive("ApprovePayment", "CreatePayment")
+            .exclusive("CreatePayment", "ApprovePayment")
+            .exclusive("Audit", "CreatePayment")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            descriptor.exclusions(),
+            &[
+                ("ApprovePayment".to_string(), "CreatePayment".to_string()),
+                ("Audit".to_string(), "CreatePayment".to_string()),
+            ]
+        );
+
+        let error = CapabilityDescriptor::builder()
+            .capability("CreatePayment", 0x1)
+            .exclusive("CreatePayment", "ApprovePayment")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DescriptorError::UndefinedCapability {
+                name: "ApprovePayment".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_composites() {
+        let descriptor = CapabilityDescriptor::builder()
+            .composite("ReadWrite", 0x3)
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .composite("All", 0x7)
+            .build()
+            .unwrap();
+
+        assert_eq!(descriptor.get("ReadWrite"), Some(&0x3));
+        assert_eq!(descriptor.composite("All"), Some(&0x7));
+        assert_eq!(descriptor.composite("Read"), None);
+        assert!(descriptor.contains("ReadWrite"));
+        assert_eq!(descriptor.len(), 3);
+        assert_eq!(descriptor.composites().count(), 2);
+        assert!(!descriptor.as_map().contains_key("ReadWrite"));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_composites() {
+        let base = || {
+            CapabilityDescriptor::builder()
+                .capability("Read", 0x1)
+                .capability("Write", 0x2)
+        };
+        let cases: Vec<(CapabilityDescriptorBuilder, DescriptorError)> = vec![
+            (
+                base().composite("Read", 0x3),
+                DescriptorError::DuplicateName {
+                    name: "Read".to_string(),
+                },
+            ),
+            (
+                base().composite("Both", 0x3).composite("Both", 0x3),
+                DescriptorError::DuplicateName {
+                    name: "Both".to_string(),
+                },
+            ),
+            (
+                base().composite("Nothing", 0x0),
+                DescriptorError::ZeroValue {
+                    name: "Nothing".to_string(),
+                },
+            ),
+            (
+                base().composite("Everything", -1),
+                DescriptorError::NegativeValue {
+                    name: "Everything".to_string(),
+                },
+            ),
+            (
+                base().composite("ReadExecute", 0x5),
+                DescriptorError::UndefinedBits {
+                    name: "ReadExecute".to_string(),
+                    bits: vec![2],
+                },
+            ),
+        ];
+
+        for (builder, expected) in cases {
+            assert_eq!(builder.build().unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn test_iteration() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .build()
+            .unwrap();
+
+        let mut names: Vec<&CapabilityName> = descriptor.names().collect();
+        names.sort();
+        assert_eq!(names, vec!["Read", "Write"]);
+
+        let total: i32 = (&descriptor).into_iter().map(|(_, value)| value).sum();
+        assert_eq!(total, 0x3);
+        assert_eq!(descriptor.values().count(), 2);
+        assert!(CapabilityDescriptor::<u64>::default().is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+//! # Diff Module
+//!
+//! This module compares two permission values and reports which capabilities were added,
+//! which were removed and which were kept. It is meant for showing role edits to a
+//! reviewer and for recording them in an audit trail.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     diff::diff_roles,
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Write", 0x2)
+//!     .capability("Admin", 0x8)
+//!     .build()
+//!     .unwrap();
+//!
+//! let before = RoleCapability::new(descriptor.clone(), 0x3);
+//! let after = RoleCapability::new(descriptor, 0x9);
+//!
+//! let diff = diff_roles(&before, &after).unwrap();
+//! assert_eq!(diff.added, vec!["Admin"]);
+//! assert_eq!(diff.removed, vec!["Write"]);
+//! assert_eq!(diff.unchanged, vec!["Read"]);
+//! assert_eq!(diff.to_string(), "+ Admin\n- Write\n  Read\n");
+//! ```
+
+use std::fmt;
+
+use crate::algebra::DescriptorMismatchError;
+use crate::level::level_name;
+use crate::models::{CapabilityDescriptor, CapabilityName};
+use crate::role_capability::RoleCapability;
+use crate::value::PermissionValue;
+
+/// The capabilities added, removed and kept between two permission values.
+///
+/// Each list is ordered by bit position. With the `serde` feature the diff serializes to
+/// `{"added": [...], "removed": [...], "unchanged": [...]}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermissionDiff {
+    /// Capabilities held after the change but not before.
+    pub added: Vec<CapabilityName>,
+    /// Capabilities held before the change but not after.
+    pub removed: Vec<CapabilityName>,
+    /// Capabilities held both before and after the change.
+    pub unchanged: Vec<CapabilityName>,
+}
+
+impl PermissionDiff {
+    /// Returns `true` if no capability was added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Renders one line per capability: `+ Name` for added, `- Name` for removed and
+/// `  Name` for unchanged capabilities.
+impl fmt::Display for PermissionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in &self.added {
+            writeln!(f, "+ {}", name)?;
+        }
+        for name in &self.removed {
+            writeln!(f, "- {}", name)?;
+        }
+        for name in &self.unchanged {
+            writeln!(f, "  {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two permission values interpreted against the same descriptor.
+///
+/// Bits that the descriptor does not define are ignored. A level field is listed as
+/// `Field=level`, so a change of level shows as the old level removed and the new one
+/// added.
+///
+/// # Arguments
+///
+/// * `before` - The value before the change
+/// * `after` - The value after the change
+/// * `descriptor` - The descriptor both values are interpreted against
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{diff::diff_values, models::CapabilityDescriptor};
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("Write", 0x2)
+///     .build()
+///     .unwrap();
+///
+/// let diff = diff_values(&0x1, &0x3, &descriptor);
+/// assert_eq!(diff.added, vec!["Write"]);
+/// assert!(diff.removed.is_empty());
+/// ```
+pub fn diff_values<V: PermissionValue>(
+    before: &V,
+    after: &V,
+    descriptor: &CapabilityDescriptor<V>,
+) -> PermissionDiff {
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut unchanged = Vec::new();
+    for (name, value) in descriptor.sorted_entries() {
+        let bit = value.bit_positions().first().copied();
+        match (before.intersects(value), after.intersects(value)) {
+            (false, true) => added.push((bit, name.clone())),
+            (true, false) => removed.push((bit, name.clone())),
+            (true, true) => unchanged.push((bit, name.clone())),
+            (false, false) => {}
+        }
+    }
+    for field in descriptor.level_fields() {
+        let bit = field.mask().bit_positions().first().copied();
+        match (level_name(field, before), level_name(field, after)) {
+            (Some(old), Some(new)) if old == new => unchanged.push((bit, new)),
+            (old, new) => {
+                removed.extend(old.map(|name| (bit, name)));
+                added.extend(new.map(|name| (bit, name)));
+            }
+        }
+    }
+    PermissionDiff {
+        added: in_bit_order(added),
+        removed: in_bit_order(removed),
+        unchanged: in_bit_order(unchanged),
+    }
+}
+
+fn in_bit_order(mut names: Vec<(Option<u32>, CapabilityName)>) -> Vec<CapabilityName> {
+    names.sort();
+    names.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Compares two roles that use the same descriptor.
+///
+/// # Errors
+///
+/// Returns [`DescriptorMismatchError`] if the roles use different descriptors.
+pub fn diff_roles<V: PermissionValue>(
+    before: &RoleCapability<V>,
+    after: &RoleCapability<V>,
+) -> Result<PermissionDiff, DescriptorMismatchError> {
+    if before.descriptor() != after.descriptor() {
+        return Err(DescriptorMismatchError);
+    }
+    Ok(diff_values(
+        &before.hex_value,
+        &after.hex_value,
+        before.descriptor(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let descriptor = create_test_descriptor();
+
+        let diff = diff_values(&0x7, &0xD, &descriptor);
+        assert_eq!(diff.added, vec!["Admin"]);
+        assert_eq!(diff.removed, vec!["Write"]);
+        assert_eq!(diff.unchanged, vec!["Read", "Execute"]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_without_changes() {
+        let descriptor = create_test_descriptor();
+
+        let diff = diff_values(&0x5, &0x5, &descriptor);
+        assert!(diff.is_empty());
+        assert_eq!(diff.unchanged, vec!["Read", "Execute"]);
+
+        let empty = diff_values(&0x0, &0x0, &descriptor);
+        assert_eq!(empty, PermissionDiff::default());
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn test_diff_ignores_undefined_bits() {
+        let descriptor = create_test_descriptor();
+
+        let diff = diff_values(&0x1, &0x31, &descriptor);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_text() {
+        let descriptor = create_test_descriptor();
+
+        let diff = diff_values(&0x3, &0xC, &descriptor);
+        assert_eq!(diff.to_string(), "+ Execute\n+ Admin\n- Read\n- Write\n");
+    }
+
+    #[test]
+    fn test_diff_level_fields() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Login", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .capability("Share", 0x8)
+            .build()
+            .unwrap();
+
+        let diff = diff_values(&0x3, &0xF, &descriptor);
+        assert_eq!(diff.added, vec!["FileAccess=admin", "Share"]);
+        assert_eq!(diff.removed, vec!["FileAccess=read"]);
+        assert_eq!(diff.unchanged, vec!["Login"]);
+
+        // Lowering a field to its "none" level only removes the old level
+        let before = RoleCapability::from_names(descriptor.clone(), ["FileAccess=write"]).unwrap();
+        let after = RoleCapability::new(descriptor, 0x0);
+        let diff = diff_roles(&before, &after).unwrap();
+        assert_eq!(diff.to_string(), "- FileAccess=write\n");
+
+        let diff = diff_roles(&before, &before).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.unchanged, vec!["FileAccess=write"]);
+    }
+
+    #[test]
+    fn test_diff_roles() {
+        let descriptor = create_test_descriptor();
+        let before = RoleCapability::new(descriptor.clone(), 0x1);
+        let after = RoleCapability::new(descriptor, 0x3);
+
+        let diff = diff_roles(&before, &after).unwrap();
+        assert_eq!(diff.added, vec!["Write"]);
+        assert_eq!(diff.unchanged, vec!["Read"]);
+
+        let reverse = diff_roles(&after, &before).unwrap();
+        assert_eq!(reverse.removed, vec!["Write"]);
+    }
+
+    #[test]
+    fn test_diff_roles_with_different_descriptors() {
+        let other = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .build()
+            .unwrap();
+        let before = RoleCapability::new(create_test_descriptor(), 0x1);
+        let after = RoleCapability::new(other, 0x1);
+
+        assert_eq!(diff_roles(&before, &after), Err(DescriptorMismatchError));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_diff_json() {
+        let descriptor = create_test_descriptor();
+        let diff = diff_values(&0x3, &0x9, &descriptor);
+
+        let json = serde_json::to_string(&diff).unwrap();
+        assert_eq!(
+            json,
+            r#"{"added":["Admin"],"removed":["Write"],"unchanged":["Read"]}"#
+        );
+        let back: PermissionDiff = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, diff);
+    }
+}
@@ -0,0 +1,532 @@
+//! # Effective Module
+//!
+//! This module computes a member's effective permissions from an implicit base role
+//! (such as "everyone") and the roles assigned to them, optionally adjusted by the
+//! [`OverwriteLayers`] of a scoped resource. Bypass capabilities of the descriptor are
+//! expanded last, so capabilities they grant cannot be removed by an overwrite. The result
+//! is a regular [`RoleCapability`] together with the roles, overwrites and bypasses that
+//! contributed each capability.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     effective::{PermissionCalculator, PermissionSource},
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Write", 0x2)
+//!     .capability("Kick", 0x4)
+//!     .build()
+//!     .unwrap();
+//!
+//! let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+//! let writer = RoleCapability::new(descriptor.clone(), 0x3);
+//! let moderator = RoleCapability::new(descriptor, 0x4);
+//!
+//! let effective = PermissionCalculator::new(&everyone)
+//!     .role("Writer", &writer)
+//!     .role("Moderator", &moderator)
+//!     .calculate()
+//!     .unwrap();
+//!
+//! assert_eq!(effective.hex_value(), &0x7);
+//! assert_eq!(
+//!     effective.sources_of("Read"),
+//!     &[PermissionSource::Base, PermissionSource::Role("Writer".to_string())]
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::algebra::DescriptorMismatchError;
+use crate::level::{level_name, split_level, union_levels, LevelField};
+use crate::models::{CapabilityDescriptor, CapabilityName, CapilityHexValue};
+use crate::overwrite::{OverwriteLayer, OverwriteLayers};
+use crate::role_capability::RoleCapability;
+use crate::value::PermissionValue;
+
+/// Where an effective capability came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PermissionSource {
+    /// The implicit base role every member holds.
+    Base,
+    /// An assigned role, by name.
+    Role(String),
+    /// An allow mask of a resource overwrite.
+    Overwrite(OverwriteLayer),
+    /// A held bypass capability, by name.
+    Bypass(CapabilityName),
+}
+
+impl fmt::Display for PermissionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermissionSource::Base => write!(f, "base role"),
+            PermissionSource::Role(name) => write!(f, "role {:?}", name),
+            PermissionSource::Overwrite(layer) => write!(f, "{}", layer),
+            PermissionSource::Bypass(name) => write!(f, "bypass {:?}", name),
+        }
+    }
+}
+
+/// The result of a [`PermissionCalculator`]: the effective role and the sources of each
+/// capability it holds.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EffectivePermissions<V = CapilityHexValue> {
+    role: RoleCapability<V>,
+    sources: BTreeMap<CapabilityName, Vec<PermissionSource>>,
+}
+
+impl<V: PermissionValue> EffectivePermissions<V> {
+    /// Returns the effective role.
+    pub fn role(&self) -> &RoleCapability<V> {
+        &self.role
+    }
+
+    /// Returns the effective permission value.
+    pub fn hex_value(&self) -> &V {
+        &self.role.hex_value
+    }
+
+    /// Returns `true` if the effective role has the named capability.
+    pub fn has_capability(&self, name: &str) -> bool {
+        self.sources.contains_key(name)
+    }
+
+    /// Returns the sources that granted the named capability, in the order they were
+    /// applied. Empty if the capability is not held.
+    pub fn sources_of(&self, name: &str) -> &[PermissionSource] {
+        self.sources.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns every held capability with its sources, ordered by name.
+    pub fn sources(&self) -> &BTreeMap<CapabilityName, Vec<PermissionSource>> {
+        &self.sources
+    }
+
+    /// Consumes the result and returns the effective role.
+    pub fn into_role(self) -> RoleCapability<V> {
+        self.role
+    }
+}
+
+impl<V: PermissionValue> fmt::Debug for EffectivePermissions<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EffectivePermissions")
+            .field("role", &self.role)
+            .field("sources", &self.sources)
+            .finish()
+    }
+}
+
+/// Computes effective permissions from a base role and a list of assigned roles.
+///
+/// The effective value is the bitwise OR of the base role and every assigned role, with
+/// each level field set to the highest level any of them holds. When overwrites are set,
+/// they are applied to that value in their precedence order; a denied capability loses
+/// its sources. Finally, every bypass capability that is still held adds the
+/// capabilities it grants. All roles must use the same descriptor.
+///
+/// Level fields are recorded as `Field=level`, with the sources that hold the field at
+/// the level it ends up at.
+pub struct PermissionCalculator<'a, V = CapilityHexValue> {
+    pub(crate) base: &'a RoleCapability<V>,
+    pub(crate) roles: Vec<(String, &'a RoleCapability<V>)>,
+    pub(crate) overwrites: Option<&'a OverwriteLayers<V>>,
+}
+
+impl<'a, V: PermissionValue> PermissionCalculator<'a, V> {
+    /// Creates a calculator for a member holding only the base role.
+    pub fn new(base: &'a RoleCapability<V>) -> Self {
+        PermissionCalculator {
+            base,
+            roles: Vec::new(),
+            overwrites: None,
+        }
+    }
+
+    /// Adds an assigned role.
+    pub fn role(mut self, name: impl Into<String>, role: &'a RoleCapability<V>) -> Self {
+        self.roles.push((name.into(), role));
+        self
+    }
+
+    /// Adds several assigned roles.
+    pub fn roles<I, S>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = (S, &'a RoleCapability<V>)>,
+        S: Into<String>,
+    {
+        self.roles
+            .extend(roles.into_iter().map(|(name, role)| (name.into(), role)));
+        self
+    }
+
+    /// Sets the overwrites of the resource the permissions are computed for.
+    pub fn overwrites(mut self, overwrites: &'a OverwriteLayers<V>) -> Self {
+        self.overwrites = Some(overwrites);
+        self
+    }
+
+    /// Computes the effective permissions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DescriptorMismatchError`] if an assigned role uses a different
+    /// descriptor from the base role.
+    pub fn calculate(&self) -> Result<EffectivePermissions<V>, DescriptorMismatchError> {
+        let descriptor = self.base.descriptor();
+        let mut value = self.base.hex_value.clone();
+        let mut sources: BTreeMap<CapabilityName, Vec<PermissionSource>> = BTreeMap::new();
+        record_sources(&mut sources, descriptor, &value, PermissionSource::Base);
+
+        for (name, role) in &self.roles {
+            if role.descriptor() != descriptor {
+                return Err(DescriptorMismatchError);
+            }
+            value = union_levels(descriptor.level_fields(), &value, &role.hex_value);
+            record_sources(
+                &mut sources,
+                descriptor,
+                &role.hex_value,
+                PermissionSource::Role(name.clone()),
+            );
+        }
+
+        if let Some(overwrites) = self.overwrites {
+            for step in overwrites.steps() {
+                for (_, overwrite) in &step.denies {
+                    value = value.and_not(overwrite.deny());
+                    for (name, capability) in descriptor {
+                        if overwrite.deny().intersects(capability) {
+                            sources.remove(name);
+                        }
+                    }
+                    for field in descriptor.level_fields() {
+                        if overwrite.deny().intersects(field.mask()) {
+                            sources.retain(|name, _| !is_level_of(name, field));
+                        }
+                    }
+                }
+                for (layer, overwrite) in step.allows {
+                    value = overwrite.grant(&value);
+                    record_sources(
+                        &mut sources,
+                        descriptor,
+                        overwrite.allow(),
+                        PermissionSource::Overwrite(layer),
+                    );
+                }
+            }
+        }
+
+        let held = value.clone();
+        for (name, _) in descriptor.bypasses() {
+            let is_held = descriptor.get(name).is_some_and(|bit| held.intersects(bit));
+            if let (true, Some(granted)) = (is_held, descriptor.bypass_grants(name)) {
+                value = value.or(&granted);
+                record_sources(
+                    &mut sources,
+                    descriptor,
+                    &granted.and_not(&held),
+                    PermissionSource::Bypass(name.clone()),
+                );
+            }
+        }
+
+        // Only the sources of the level each field ends up at are kept
+        for field in descriptor.level_fields() {
+            let held = level_name(field, &value);
+            sources.retain(|name, _| !is_level_of(name, field) || Some(name) == held.as_ref());
+        }
+
+        Ok(EffectivePermissions {
+            role: RoleCapability::new(descriptor.clone(), value),
+            sources,
+        })
+    }
+}
+
+fn record_sources<V: PermissionValue>(
+    sources: &mut BTreeMap<CapabilityName, Vec<PermissionSource>>,
+    descriptor: &CapabilityDescriptor<V>,
+    value: &V,
+    source: PermissionSource,
+) {
+    for (name, capability) in descriptor {
+        if value.intersects(capability) {
+            sources
+                .entry(name.clone())
+                .or_default()
+                .push(source.clone());
+        }
+    }
+    for field in descriptor.level_fields() {
+        if let Some(name) = level_name(field, value) {
+            sources.entry(name).or_default().push(source.clone());
+        }
+    }
+}
+
+/// Returns `true` if the name is a `Field=level` name of the field.
+fn is_level_of<V: PermissionValue>(name: &str, field: &LevelField<V>) -> bool {
+    split_level(name).is_some_and(|(name, _)| name == field.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::overwrite::PermissionOverwrite;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_base_role_only() {
+        let everyone = RoleCapability::new(create_test_descriptor(), 0x1);
+
+        let effective = PermissionCalculator::new(&everyone).calculate().unwrap();
+        assert_eq!(effective.hex_value(), &0x1);
+        assert_eq!(effective.sources_of("Read"), &[PermissionSource::Base]);
+        assert!(effective.sources_of("Write").is_empty());
+        assert!(!effective.has_capability("Write"));
+    }
+
+    #[test]
+    fn test_roles_are_combined() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+        let writer = RoleCapability::new(descriptor.clone(), 0x3);
+        let runner = RoleCapability::new(descriptor, 0x5);
+
+        let effective = PermissionCalculator::new(&everyone)
+            .roles([("Writer", &writer), ("Runner", &runner)])
+            .calculate()
+            .unwrap();
+
+        assert_eq!(effective.hex_value(), &0x7);
+        assert!(effective.role().has_capability(&"Execute".to_string()));
+        assert_eq!(
+            effective.sources_of("Read"),
+            &[
+                PermissionSource::Base,
+                PermissionSource::Role("Writer".to_string()),
+                PermissionSource::Role("Runner".to_string()),
+            ]
+        );
+        assert_eq!(
+            effective.sources_of("Execute"),
+            &[PermissionSource::Role("Runner".to_string())]
+        );
+        assert_eq!(
+            effective.sources().keys().collect::<Vec<_>>(),
+            vec!["Execute", "Read", "Write"]
+        );
+    }
+
+    #[test]
+    fn test_roles_combine_levels_by_maximum() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Login", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+        let reader = RoleCapability::from_names(descriptor.clone(), ["FileAccess=read"]).unwrap();
+        let writer = RoleCapability::from_names(descriptor, ["FileAccess=write"]).unwrap();
+
+        // read (0b01) and write (0b10) give write, not admin (0b11)
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Reader", &reader)
+            .role("Writer", &writer)
+            .calculate()
+            .unwrap();
+        assert_eq!(effective.hex_value(), &0x5);
+        assert_eq!(
+            effective.role().level("FileAccess").map(String::as_str),
+            Some("write")
+        );
+    }
+
+    #[test]
+    fn test_level_sources() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Login", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+        let everyone = RoleCapability::from_names(descriptor.clone(), ["FileAccess=read"]).unwrap();
+        let writer = RoleCapability::from_names(descriptor.clone(), ["FileAccess=write"]).unwrap();
+        let editor = RoleCapability::from_names(descriptor.clone(), ["FileAccess=write"]).unwrap();
+
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Writer", &writer)
+            .role("Editor", &editor)
+            .calculate()
+            .unwrap();
+        assert_eq!(
+            effective.sources_of("FileAccess=write"),
+            &[
+                PermissionSource::Role("Writer".to_string()),
+                PermissionSource::Role("Editor".to_string()),
+            ]
+        );
+        assert!(effective.has_capability("FileAccess=write"));
+        assert!(effective.sources_of("FileAccess=read").is_empty());
+
+        // A denied field loses its sources; an allowed level records the overwrite
+        let overwrites = OverwriteLayers::new()
+            .everyone(PermissionOverwrite::new(0x0, 0x6, &descriptor).unwrap())
+            .member(
+                PermissionOverwrite::from_names(["FileAccess=admin"], [] as [&str; 0], &descriptor)
+                    .unwrap(),
+            );
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Writer", &writer)
+            .overwrites(&overwrites)
+            .calculate()
+            .unwrap();
+        assert_eq!(
+            effective.sources_of("FileAccess=admin"),
+            &[PermissionSource::Overwrite(OverwriteLayer::Member)]
+        );
+        assert_eq!(
+            effective.sources().keys().collect::<Vec<_>>(),
+            vec!["FileAccess=admin"]
+        );
+    }
+
+    #[test]
+    fn test_mismatched_descriptor() {
+        let other = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .build()
+            .unwrap();
+        let everyone = RoleCapability::new(create_test_descriptor(), 0x1);
+        let stranger = RoleCapability::new(other, 0x1);
+
+        let result = PermissionCalculator::new(&everyone)
+            .role("Stranger", &stranger)
+            .calculate();
+        assert_eq!(result, Err(DescriptorMismatchError));
+    }
+
+    #[test]
+    fn test_overwrites_adjust_effective_permissions() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x3);
+        let runner = RoleCapability::new(descriptor.clone(), 0x4);
+        let layers = OverwriteLayers::new()
+            .everyone(PermissionOverwrite::new(0x0, 0x2, &descriptor).unwrap())
+            .role(
+                "Runner",
+                PermissionOverwrite::new(0x8, 0x0, &descriptor).unwrap(),
+            );
+
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Runner", &runner)
+            .overwrites(&layers)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(effective.hex_value(), &0xD);
+        assert!(!effective.has_capability("Write"));
+        assert_eq!(
+            effective.sources_of("Admin"),
+            &[PermissionSource::Overwrite(OverwriteLayer::Role(
+                "Runner".to_string()
+            ))]
+        );
+        assert_eq!(effective.sources_of("Read"), &[PermissionSource::Base]);
+    }
+
+    #[test]
+    fn test_denied_then_allowed_capability_keeps_only_overwrite_source() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x2);
+        let layers = OverwriteLayers::new()
+            .everyone(PermissionOverwrite::new(0x0, 0x2, &descriptor).unwrap())
+            .member(PermissionOverwrite::new(0x2, 0x0, &descriptor).unwrap());
+
+        let effective = PermissionCalculator::new(&everyone)
+            .overwrites(&layers)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(effective.hex_value(), &0x2);
+        assert_eq!(
+            effective.sources_of("Write"),
+            &[PermissionSource::Overwrite(OverwriteLayer::Member)]
+        );
+    }
+
+    #[test]
+    fn test_bypass_grants_are_recorded_and_survive_denies() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .grants_all("Admin")
+            .build()
+            .unwrap();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+        let owner = RoleCapability::new(descriptor.clone(), 0x8);
+        let layers = OverwriteLayers::new()
+            .everyone(PermissionOverwrite::new(0x0, 0x2, &descriptor).unwrap());
+
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Owner", &owner)
+            .overwrites(&layers)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(effective.hex_value(), &0xF);
+        assert_eq!(effective.sources_of("Read"), &[PermissionSource::Base]);
+        assert_eq!(
+            effective.sources_of("Write"),
+            &[PermissionSource::Bypass("Admin".to_string())]
+        );
+        assert_eq!(
+            effective.sources_of("Admin"),
+            &[PermissionSource::Role("Owner".to_string())]
+        );
+
+        // A denied bypass grants nothing
+        let deny_admin =
+            OverwriteLayers::new().member(PermissionOverwrite::new(0x0, 0x8, &descriptor).unwrap());
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Owner", &owner)
+            .overwrites(&deny_admin)
+            .calculate()
+            .unwrap();
+        assert_eq!(effective.hex_value(), &0x1);
+    }
+
+    #[test]
+    fn test_source_display() {
+        assert_eq!(PermissionSource::Base.to_string(), "base role");
+        assert_eq!(
+            PermissionSource::Role("Mods".to_string()).to_string(),
+            "role \"Mods\""
+        );
+        assert_eq!(
+            PermissionSource::Bypass("Admin".to_string()).to_string(),
+            "bypass \"Admin\""
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! # Exclusive Module
+//!
+//! This module checks the mutually exclusive capability pairs of a descriptor, declared
+//! with [`exclusive`](crate::models::CapabilityDescriptorBuilder::exclusive), against a
+//! single role or against the effective permissions of several roles combined. Each pair
+//! held together is reported as an [`ExclusivityViolation`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     effective::PermissionCalculator,
+//!     exclusive::ExclusivityViolation,
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("CreatePayment", 0x1)
+//!     .capability("ApprovePayment", 0x2)
+//!     .exclusive("CreatePayment", "ApprovePayment")
+//!     .build()
+//!     .unwrap();
+//!
+//! let clerk = RoleCapability::new(descriptor.clone(), 0x1);
+//! let manager = RoleCapability::new(descriptor.clone(), 0x2);
+//! assert!(clerk.exclusivity_violations().is_empty());
+//! assert!(manager.exclusivity_violations().is_empty());
+//!
+//! // Each role is compliant, but a member holding both is not
+//! let everyone = RoleCapability::new(descriptor, 0x0);
+//! let effective = PermissionCalculator::new(&everyone)
+//!     .role("Clerk", &clerk)
+//!     .role("Manager", &manager)
+//!     .calculate()
+//!     .unwrap();
+//! assert_eq!(
+//!     effective.exclusivity_violations(),
+//!     vec![ExclusivityViolation {
+//!         first: "CreatePayment".to_string(),
+//!         second: "ApprovePayment".to_string(),
+//!     }]
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::effective::EffectivePermissions;
+use crate::models::{CapabilityDescriptor, CapabilityName};
+use crate::role_capability::RoleCapability;
+use crate::value::PermissionValue;
+
+/// A pair of mutually exclusive capabilities that are held together.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExclusivityViolation {
+    /// The first capability of the pair, as declared.
+    pub first: CapabilityName,
+    /// The second capability of the pair, as declared.
+    pub second: CapabilityName,
+}
+
+impl fmt::Display for ExclusivityViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} and {} are mutually exclusive",
+            self.first, self.second
+        )
+    }
+}
+
+impl<V: PermissionValue> RoleCapability<V> {
+    /// Returns every mutually exclusive pair the role holds, in declaration order.
+    ///
+    /// A capability counts as held if [`has_capability`](RoleCapability::has_capability)
+    /// returns `true` for it, so a bypass that grants both capabilities of a pair is
+    /// reported.
+    pub fn exclusivity_violations(&self) -> Vec<ExclusivityViolation> {
+        find_violations(self.descriptor(), &self.granted_value())
+    }
+}
+
+impl<V: PermissionValue> EffectivePermissions<V> {
+    /// Returns every mutually exclusive pair held by the combined roles, in declaration
+    /// order.
+    ///
+    /// Use [`sources_of`](EffectivePermissions::sources_of) to find which roles granted
+    /// each side of a pair.
+    pub fn exclusivity_violations(&self) -> Vec<ExclusivityViolation> {
+        self.role().exclusivity_violations()
+    }
+}
+
+fn find_violations<V: PermissionValue>(
+    descriptor: &CapabilityDescriptor<V>,
+    value: &V,
+) -> Vec<ExclusivityViolation> {
+    let holds = |name: &str| {
+        descriptor
+            .get(name)
+            .is_some_and(|bit| value.intersects(bit))
+    };
+    descriptor
+        .exclusions()
+        .iter()
+        .filter(|(first, second)| holds(first) && holds(second))
+        .map(|(first, second)| ExclusivityViolation {
+            first: first.clone(),
+            second: second.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effective::PermissionCalculator;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Create", 0x1)
+            .capability("Approve", 0x2)
+            .capability("Audit", 0x4)
+            .capability("Admin", 0x8)
+            .exclusive("Create", "Approve")
+            .exclusive("Approve", "Audit")
+            .grants_all("Admin")
+            .build()
+            .unwrap()
+    }
+
+    fn violation(first: &str, second: &str) -> ExclusivityViolation {
+        ExclusivityViolation {
+            first: first.to_string(),
+            second: second.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_single_role() {
+        let descriptor = create_test_descriptor();
+
+        let compliant = RoleCapability::new(descriptor.clone(), 0x5);
+        assert!(compliant.exclusivity_violations().is_empty());
+
+        let both = RoleCapability::new(descriptor.clone(), 0x3);
+        assert_eq!(
+            both.exclusivity_violations(),
+            vec![violation("Create", "Approve")]
+        );
+
+        let all = RoleCapability::new(descriptor, 0x7);
+        assert_eq!(
+            all.exclusivity_violations(),
+            vec![
+                violation("Create", "Approve"),
+                violation("Approve", "Audit")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bypass_counts_as_holding_both() {
+        let admin = RoleCapability::new(create_test_descriptor(), 0x8);
+
+        assert_eq!(admin.exclusivity_violations().len(), 2);
+    }
+
+    #[test]
+    fn test_combined_roles() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x4);
+        let approver = RoleCapability::new(descriptor.clone(), 0x2);
+
+        let effective = PermissionCalculator::new(&everyone)
+            .role("Approver", &approver)
+            .calculate()
+            .unwrap();
+        assert_eq!(
+            effective.exclusivity_violations(),
+            vec![violation("Approve", "Audit")]
+        );
+        assert_eq!(
+            violation("Approve", "Audit").to_string(),
+            "Approve and Audit are mutually exclusive"
+        );
+    }
+}
@@ -0,0 +1,485 @@
+//! # Explain Module
+//!
+//! This module answers "why can (or can't) this member do X?". Given the same inputs as
+//! [`PermissionCalculator::calculate`], [`PermissionCalculator::explain`] returns an
+//! [`Explanation`] listing, in the order they were applied, the roles and overwrites that
+//! set the capability, the denies that removed it, the bypasses that granted it and the
+//! held capabilities that imply it. The trace renders as text and, with the `serde`
+//! feature, serializes to JSON.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     effective::PermissionCalculator,
+//!     models::CapabilityDescriptor,
+//!     overwrite::{OverwriteLayers, PermissionOverwrite},
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("View", 0x1)
+//!     .capability("Send", 0x2)
+//!     .build()
+//!     .unwrap();
+//!
+//! let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+//! let writer = RoleCapability::new(descriptor.clone(), 0x3);
+//! let layers = OverwriteLayers::new()
+//!     .everyone(PermissionOverwrite::from_names([] as [&str; 0], ["Send"], &descriptor).unwrap());
+//!
+//! let explanation = PermissionCalculator::new(&everyone)
+//!     .role("Writer", &writer)
+//!     .overwrites(&layers)
+//!     .explain("Send")
+//!     .unwrap();
+//!
+//! assert!(!explanation.granted);
+//! assert_eq!(
+//!     explanation.to_string(),
+//!     "Send is not granted\n  \
+//!        granted by role \"Writer\"\n  \
+//!        denied by everyone overwrite\n"
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::algebra::DescriptorMismatchError;
+use crate::effective::{PermissionCalculator, PermissionSource};
+use crate::level::split_level;
+use crate::models::CapabilityName;
+use crate::namespace::split_wildcard;
+use crate::overwrite::OverwriteLayer;
+use crate::role_capability::UnknownCapabilityError;
+use crate::value::PermissionValue;
+
+/// One step of an [`Explanation`].
+///
+/// With the `serde` feature a step serializes with a `step` tag, such as
+/// `{"step":"denied","layer":"everyone"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "step", rename_all = "lowercase"))]
+pub enum TraceStep {
+    /// A role, an overwrite allow or a held bypass set the capability.
+    Granted { source: PermissionSource },
+    /// An overwrite deny removed the capability.
+    Denied { layer: OverwriteLayer },
+    /// A held capability implies this one. Implications only count for
+    /// [`CapabilityMatch::Implied`](crate::role_capability::CapabilityMatch::Implied)
+    /// checks, so this step does not make the capability granted.
+    Implied { by: CapabilityName },
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceStep::Granted { source } => write!(f, "granted by {}", source),
+            TraceStep::Denied { layer } => write!(f, "denied by {}", layer),
+            TraceStep::Implied { by } => write!(f, "implied by {:?}", by),
+        }
+    }
+}
+
+/// The decision trace for one capability, returned by [`PermissionCalculator::explain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation {
+    /// The explained capability.
+    pub capability: CapabilityName,
+    /// Whether the effective role has the capability.
+    pub granted: bool,
+    /// The steps that affected the capability, in the order they were applied.
+    pub steps: Vec<TraceStep>,
+}
+
+/// Renders a heading such as `Send is not granted`, then one indented line per step.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.granted {
+            "granted"
+        } else {
+            "not granted"
+        };
+        writeln!(f, "{} is {}", self.capability, verdict)?;
+        for step in &self.steps {
+            writeln!(f, "  {}", step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a capability cannot be explained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainError {
+    /// An assigned role uses a different descriptor from the base role.
+    DescriptorMismatch(DescriptorMismatchError),
+    /// The descriptor does not define the capability.
+    UnknownCapability(UnknownCapabilityError),
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExplainError::DescriptorMismatch(error) => write!(f, "{}", error),
+            ExplainError::UnknownCapability(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ExplainError {}
+
+impl From<DescriptorMismatchError> for ExplainError {
+    fn from(error: DescriptorMismatchError) -> Self {
+        ExplainError::DescriptorMismatch(error)
+    }
+}
+
+impl From<UnknownCapabilityError> for ExplainError {
+    fn from(error: UnknownCapabilityError) -> Self {
+        ExplainError::UnknownCapability(error)
+    }
+}
+
+impl<V: PermissionValue> PermissionCalculator<'_, V> {
+    /// Explains whether the member has the named capability, composite, `namespace.*`
+    /// wildcard or `Field=level`.
+    ///
+    /// Every overwrite deny that clears a held capability is listed, even when several
+    /// role overwrites deny it in the same step. Bypass grants are listed only when the
+    /// capability was not already held, and implications only when it is not granted.
+    /// Roles and overwrites are matched like
+    /// [`has_capability`](crate::role_capability::RoleCapability::has_capability): one
+    /// that holds any bit of a capability or composite is listed, but for a `namespace.*`
+    /// wildcard only one that holds the whole namespace on its own. For a level, a role or
+    /// overwrite is listed if it sets the field to exactly that level, and a deny if it
+    /// clears any bit of the field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExplainError`] if the roles use different descriptors or the
+    /// descriptor does not define the name.
+    pub fn explain(&self, name: &str) -> Result<Explanation, ExplainError> {
+        let effective = self.calculate()?;
+        let descriptor = self.base.descriptor();
+        let bit = descriptor
+            .resolve(name)
+            .ok_or_else(|| UnknownCapabilityError {
+                names: vec![name.to_string()],
+            })?;
+        let (field, all_bits) = match descriptor.get(name) {
+            Some(_) => (None, false),
+            None => (
+                split_level(name).and_then(|(field, _)| descriptor.level_field(field)),
+                split_wildcard(name).is_some(),
+            ),
+        };
+        // A level is set only by its exact bit pattern, but cleared by any bit of its field
+        let sets = |value: &V| match field {
+            Some(field) => !bit.is_zero() && value.and(field.mask()) == bit,
+            None if all_bits => value.contains_all(&bit),
+            None => value.intersects(&bit),
+        };
+        let clears = |value: &V| value.intersects(field.map_or(&bit, |field| field.mask()));
+
+        let mut steps = Vec::new();
+        let mut held = sets(&self.base.hex_value);
+        if held {
+            steps.push(TraceStep::Granted {
+                source: PermissionSource::Base,
+            });
+        }
+        for (role_name, role) in &self.roles {
+            if sets(&role.hex_value) {
+                held = true;
+                steps.push(TraceStep::Granted {
+                    source: PermissionSource::Role(role_name.clone()),
+                });
+            }
+        }
+
+        if let Some(overwrites) = self.overwrites {
+            for step in overwrites.steps() {
+                let was_held = held;
+                for (layer, overwrite) in step.denies {
+                    if was_held && clears(overwrite.deny()) {
+                        held = false;
+                        steps.push(TraceStep::Denied { layer });
+                    }
+                }
+                for (layer, overwrite) in step.allows {
+                    if sets(overwrite.allow()) {
+                        held = true;
+                        steps.push(TraceStep::Granted {
+                            source: PermissionSource::Overwrite(layer),
+                        });
+                    }
+                }
+            }
+        }
+
+        steps.extend(
+            effective
+                .sources_of(name)
+                .iter()
+                .filter(|source| matches!(source, PermissionSource::Bypass(_)))
+                .map(|source| TraceStep::Granted {
+                    source: source.clone(),
+                }),
+        );
+
+        let granted = effective.role().has_capability(&name.to_string());
+        if !granted && field.is_none() {
+            for (implying, _) in descriptor.implications() {
+                let implies = descriptor.get(implying).is_some_and(|implying_bit| {
+                    effective.hex_value().intersects(implying_bit)
+                        && sets(&descriptor.implied_by(implying_bit))
+                });
+                if implies {
+                    steps.push(TraceStep::Implied {
+                        by: implying.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(Explanation {
+            capability: name.to_string(),
+            granted,
+            steps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CapabilityDescriptor;
+    use crate::overwrite::{OverwriteLayers, PermissionOverwrite};
+    use crate::role_capability::RoleCapability;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .capability("Send", 0x2)
+            .capability("Manage", 0x4)
+            .capability("Admin", 0x8)
+            .capability("Edit", 0x10)
+            .grants("Admin", ["Send"])
+            .implies("Manage", ["Edit"])
+            .build()
+            .unwrap()
+    }
+
+    fn granted(source: PermissionSource) -> TraceStep {
+        TraceStep::Granted { source }
+    }
+
+    #[test]
+    fn test_roles_that_set_the_capability() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+        let writer = RoleCapability::new(descriptor, 0x3);
+
+        let explanation = PermissionCalculator::new(&everyone)
+            .role("Writer", &writer)
+            .explain("View")
+            .unwrap();
+        assert!(explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![
+                granted(PermissionSource::Base),
+                granted(PermissionSource::Role("Writer".to_string())),
+            ]
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "View is granted\n  granted by base role\n  granted by role \"Writer\"\n"
+        );
+    }
+
+    #[test]
+    fn test_overwrites_deny_and_allow() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x3);
+        let layers = OverwriteLayers::new()
+            .role(
+                "Muted",
+                PermissionOverwrite::new(0x0, 0x2, &descriptor).unwrap(),
+            )
+            .role(
+                "Quiet",
+                PermissionOverwrite::new(0x0, 0x2, &descriptor).unwrap(),
+            )
+            .member(PermissionOverwrite::new(0x2, 0x0, &descriptor).unwrap());
+
+        let explanation = PermissionCalculator::new(&everyone)
+            .overwrites(&layers)
+            .explain("Send")
+            .unwrap();
+        assert!(explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![
+                granted(PermissionSource::Base),
+                TraceStep::Denied {
+                    layer: OverwriteLayer::Role("Muted".to_string())
+                },
+                TraceStep::Denied {
+                    layer: OverwriteLayer::Role("Quiet".to_string())
+                },
+                granted(PermissionSource::Overwrite(OverwriteLayer::Member)),
+            ]
+        );
+
+        // A deny of a capability that is not held is not part of the trace
+        let nobody = RoleCapability::new(descriptor, 0x0);
+        let explanation = PermissionCalculator::new(&nobody)
+            .overwrites(&layers)
+            .explain("View")
+            .unwrap();
+        assert!(!explanation.granted);
+        assert!(explanation.steps.is_empty());
+        assert_eq!(explanation.to_string(), "View is not granted\n");
+    }
+
+    #[test]
+    fn test_bypass_and_implication() {
+        let descriptor = create_test_descriptor();
+        let everyone = RoleCapability::new(descriptor.clone(), 0x0);
+        let admin = RoleCapability::new(descriptor.clone(), 0x8);
+        let manager = RoleCapability::new(descriptor, 0x4);
+
+        let explanation = PermissionCalculator::new(&everyone)
+            .role("Admin", &admin)
+            .explain("Send")
+            .unwrap();
+        assert!(explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![granted(PermissionSource::Bypass("Admin".to_string()))]
+        );
+
+        let explanation = PermissionCalculator::new(&everyone)
+            .role("Manager", &manager)
+            .explain("Edit")
+            .unwrap();
+        assert!(!explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![TraceStep::Implied {
+                by: "Manage".to_string()
+            }]
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "Edit is not granted\n  implied by \"Manage\"\n"
+        );
+    }
+
+    #[test]
+    fn test_levels_and_wildcards() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("files.read", 0x1)
+            .capability("files.write", 0x2)
+            .level_field("Support", 0xC, ["none", "agent", "lead"])
+            .composite("FileEditing", 0x3)
+            .build()
+            .unwrap();
+        let everyone = RoleCapability::from_names(descriptor.clone(), ["Support=agent"]).unwrap();
+        let lead =
+            RoleCapability::from_names(descriptor.clone(), ["files.read", "Support=lead"]).unwrap();
+        let writer = RoleCapability::from_names(descriptor.clone(), ["files.write"]).unwrap();
+
+        let explanation = PermissionCalculator::new(&everyone)
+            .role("Lead", &lead)
+            .explain("Support=lead")
+            .unwrap();
+        assert!(explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![granted(PermissionSource::Role("Lead".to_string()))]
+        );
+
+        // Any deny in the field clears the level
+        let layers =
+            OverwriteLayers::new().member(PermissionOverwrite::new(0x0, 0x4, &descriptor).unwrap());
+        let explanation = PermissionCalculator::new(&everyone)
+            .overwrites(&layers)
+            .explain("Support=agent")
+            .unwrap();
+        assert!(!explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![
+                granted(PermissionSource::Base),
+                TraceStep::Denied {
+                    layer: OverwriteLayer::Member
+                },
+            ]
+        );
+
+        // A partly held namespace is not granted, and neither role grants it on its own
+        let explanation = PermissionCalculator::new(&everyone)
+            .role("Lead", &lead)
+            .explain("files.*")
+            .unwrap();
+        assert!(!explanation.granted);
+        assert!(explanation.steps.is_empty());
+        assert_eq!(explanation.to_string(), "files.* is not granted\n");
+
+        // A composite, like has_capability, is granted by any of its bits
+        let explanation = PermissionCalculator::new(&everyone)
+            .role("Lead", &lead)
+            .explain("FileEditing")
+            .unwrap();
+        assert!(explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![granted(PermissionSource::Role("Lead".to_string()))]
+        );
+
+        let editor =
+            RoleCapability::from_names(descriptor.clone(), ["files.read", "files.write"]).unwrap();
+        let explanation = PermissionCalculator::new(&everyone)
+            .roles([("Lead", &lead), ("Writer", &writer), ("Editor", &editor)])
+            .explain("files.*")
+            .unwrap();
+        assert!(explanation.granted);
+        assert_eq!(
+            explanation.steps,
+            vec![granted(PermissionSource::Role("Editor".to_string()))]
+        );
+
+        let error = PermissionCalculator::new(&everyone)
+            .explain("Support=owner")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "unknown capability: Support=owner");
+    }
+
+    #[test]
+    fn test_errors() {
+        let everyone = RoleCapability::new(create_test_descriptor(), 0x1);
+        let other = CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .build()
+            .unwrap();
+        let stranger = RoleCapability::new(other, 0x1);
+
+        let error = PermissionCalculator::new(&everyone)
+            .explain("Fly")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "unknown capability: Fly");
+
+        let error = PermissionCalculator::new(&everyone)
+            .role("Stranger", &stranger)
+            .explain("View")
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ExplainError::DescriptorMismatch(DescriptorMismatchError)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! # Format Module
+//!
+//! This module renders permission values as zero-padded hexadecimal, binary or octal
+//! strings. It works for every [`PermissionValue`], including values wider than 128 bits,
+//! and is what the `Display` and `Debug` implementations of
+//! [`RoleCapability`](crate::role_capability::RoleCapability) use.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::format::{format_value, Radix};
+//!
+//! assert_eq!(format_value(&0xBi32, Radix::Hex, 32), "0x0000000B");
+//! assert_eq!(format_value(&0xBi32, Radix::Binary, 8), "0b00001011");
+//! assert_eq!(format_value(&0xBi32, Radix::Octal, 6), "0o13");
+//! ```
+
+use std::fmt;
+
+use crate::value::PermissionValue;
+
+/// A power-of-two base used to render a permission value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Radix {
+    /// Base 2, prefixed with `0b`.
+    Binary,
+    /// Base 8, prefixed with `0o`.
+    Octal,
+    /// Base 16, prefixed with `0x`.
+    Hex,
+}
+
+impl Radix {
+    /// Returns the number of bits represented by one digit.
+    pub fn bits_per_digit(self) -> u32 {
+        match self {
+            Radix::Binary => 1,
+            Radix::Octal => 3,
+            Radix::Hex => 4,
+        }
+    }
+
+    /// Returns the prefix written before the digits.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::Hex => "0x",
+        }
+    }
+}
+
+impl fmt::Display for Radix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Radix::Binary => write!(f, "binary"),
+            Radix::Octal => write!(f, "octal"),
+            Radix::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+/// Formats a value with its radix prefix, padded with zeros to cover `width_bits` bits.
+///
+/// Hex digits are upper case. The value is never truncated: if it needs more digits than
+/// `width_bits` allows, all of them are written.
+///
+/// # Arguments
+///
+/// * `value` - The permission value to format
+/// * `radix` - The base to use
+/// * `width_bits` - The number of bits the output should cover, usually the width of the value type
+pub fn format_value<V: PermissionValue>(value: &V, radix: Radix, width_bits: u32) -> String {
+    let bits_per_digit = radix.bits_per_digit();
+    let bits = width_bits.max(value.bit_len()).max(1);
+    let digit_count = bits.div_ceil(bits_per_digit);
+
+    let mut output = String::with_capacity(2 + digit_count as usize);
+    output.push_str(radix.prefix());
+    for digit_index in (0..digit_count).rev() {
+        let mut digit = 0;
+        for offset in 0..bits_per_digit {
+            if value.test_bit(digit_index * bits_per_digit + offset) {
+                digit |= 1 << offset;
+            }
+        }
+        output.push(
+            char::from_digit(digit, 1 << bits_per_digit)
+                .expect("digit is below the radix")
+                .to_ascii_uppercase(),
+        );
+    }
+    output
+}
+
+/// Returns the number of bits to pad a value of type `V` to.
+///
+/// This is the width of the type, or `fallback_bits` for types without a fixed width.
+pub fn type_width<V: PermissionValue>(fallback_bits: u32) -> u32 {
+    V::BITS.unwrap_or(fallback_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitset::CapabilityBitSet;
+
+    #[test]
+    fn test_format_hex() {
+        assert_eq!(format_value(&0xBi32, Radix::Hex, 32), "0x0000000B");
+        assert_eq!(
+            format_value(&0xABCDu64, Radix::Hex, 64),
+            "0x000000000000ABCD"
+        );
+        assert_eq!(format_value(&0u32, Radix::Hex, 0), "0x0");
+    }
+
+    #[test]
+    fn test_format_binary_and_octal() {
+        assert_eq!(format_value(&0b1011u32, Radix::Binary, 8), "0b00001011");
+        assert_eq!(format_value(&0o755u32, Radix::Octal, 9), "0o755");
+        assert_eq!(
+            format_value(&0xFFFF_FFFFu32, Radix::Octal, 32),
+            "0o37777777777"
+        );
+    }
+
+    #[test]
+    fn test_format_never_truncates() {
+        assert_eq!(format_value(&0x1FFu32, Radix::Hex, 4), "0x1FF");
+    }
+
+    #[test]
+    fn test_format_sign_bit() {
+        assert_eq!(format_value(&i32::MIN, Radix::Hex, 32), "0x80000000");
+    }
+
+    #[test]
+    fn test_format_bitset() {
+        let value: CapabilityBitSet = [0, 130].into_iter().collect();
+        let formatted = format_value(&value, Radix::Hex, 0);
+        assert_eq!(formatted.len(), 2 + 33);
+        assert!(formatted.starts_with("0x4"));
+        assert!(formatted.ends_with("01"));
+    }
+
+    #[test]
+    fn test_type_width() {
+        assert_eq!(type_width::<i32>(8), 32);
+        assert_eq!(type_width::<u128>(8), 128);
+        assert_eq!(type_width::<CapabilityBitSet>(8), 8);
+    }
+}
@@ -0,0 +1,406 @@
+//! # Level Module
+//!
+//! This module defines [`LevelField`], a multi-bit field of a descriptor whose bit patterns
+//! name ordered levels rather than independent flags, such as
+//! `FileAccess: none/read/write/admin` stored in 2 bits.
+//!
+//! Levels are numbered from 0 in declaration order, and level `n` is stored as `n` spread
+//! over the field's bits from the lowest up. A pattern above the last level is invalid and
+//! rejected by [`validate`](crate::checks::validate). On a role, a level is written as
+//! `Field=level`, which [`RoleCapability::encode_names`] accepts and
+//! [`RoleCapability::to_name_list`] produces alongside plain flags.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     checks::is_valid_hex,
+//!     models::CapabilityDescriptor,
+//!     role_capability::{CapabilityOrder, RoleCapability},
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Login", 0x1)
+//!     .level_field("FileAccess", 0x6, ["none", "read", "write"])
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut role = RoleCapability::from_names(descriptor.clone(), ["Login", "FileAccess=read"]).unwrap();
+//! assert_eq!(role.hex_value, 0x3);
+//! assert_eq!(role.level("FileAccess").map(String::as_str), Some("read"));
+//!
+//! role.set_level("FileAccess", "write").unwrap();
+//! assert_eq!(role.hex_value, 0x5);
+//! assert_eq!(role.to_name_list(CapabilityOrder::BitPosition), vec!["Login", "FileAccess=write"]);
+//!
+//! // 0b11 in the field is not a level
+//! assert!(!is_valid_hex(0x7, &descriptor));
+//! ```
+
+use crate::models::{CapabilityHexUnitValue, CapabilityName};
+use crate::role_capability::{RoleCapability, UnknownCapabilityError};
+use crate::value::PermissionValue;
+
+/// A multi-bit descriptor field holding one of several named levels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LevelField<V = CapabilityHexUnitValue> {
+    name: CapabilityName,
+    mask: V,
+    levels: Vec<CapabilityName>,
+}
+
+impl<V: PermissionValue> LevelField<V> {
+    pub(crate) fn new(name: CapabilityName, mask: V, levels: Vec<CapabilityName>) -> Self {
+        LevelField { name, mask, levels }
+    }
+
+    /// Returns the field name.
+    pub fn name(&self) -> &CapabilityName {
+        &self.name
+    }
+
+    /// Returns the bits the field occupies.
+    pub fn mask(&self) -> &V {
+        &self.mask
+    }
+
+    /// Returns the level names, lowest first.
+    pub fn levels(&self) -> &[CapabilityName] {
+        &self.levels
+    }
+
+    /// Returns the bit pattern of the level at `index`, or `None` if there is no such level.
+    pub fn level_value(&self, index: usize) -> Option<V> {
+        if index >= self.levels.len() {
+            return None;
+        }
+        let value = self
+            .mask
+            .bit_positions()
+            .into_iter()
+            .enumerate()
+            .filter(|(position, _)| (index >> position) & 1 == 1)
+            .filter_map(|(_, bit)| V::bit(bit))
+            .fold(V::zero(), |value, bit| value.or(&bit));
+        Some(value)
+    }
+
+    /// Returns the bit pattern of the named level, or `None` if the level does not exist.
+    pub fn encode(&self, level: &str) -> Option<V> {
+        let index = self.levels.iter().position(|name| name == level)?;
+        self.level_value(index)
+    }
+
+    /// Returns the level stored in the field's bits of `value`, or `None` if the bit
+    /// pattern is not a level. Bits outside the field are ignored.
+    pub fn decode(&self, value: &V) -> Option<&CapabilityName> {
+        self.levels.get(self.index_of(value)?)
+    }
+
+    /// Returns the index of the level stored in the field's bits of `value`, or `None` if
+    /// the bit pattern is not a level.
+    fn index_of(&self, value: &V) -> Option<usize> {
+        let index = self
+            .mask
+            .bit_positions()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, bit)| value.test_bit(*bit))
+            .try_fold(0usize, |index, (position, _)| {
+                1usize
+                    .checked_shl(position as u32)
+                    .map(|weight| index | weight)
+            })?;
+        (index < self.levels.len()).then_some(index)
+    }
+}
+
+/// Splits a `Field=level` name into the field and the level.
+pub(crate) fn split_level(name: &str) -> Option<(&str, &str)> {
+    name.split_once('=')
+}
+
+/// Returns the `Field=level` name of the level a value holds, or `None` if the field is
+/// at its lowest level or holds a bit pattern that is not a level.
+pub(crate) fn level_name<V: PermissionValue>(
+    field: &LevelField<V>,
+    value: &V,
+) -> Option<CapabilityName> {
+    if !value.intersects(field.mask()) {
+        return None;
+    }
+    let level = field.decode(value)?;
+    Some(format!("{}={}", field.name(), level))
+}
+
+/// Returns the bits of every level field.
+pub(crate) fn field_mask<V: PermissionValue>(fields: &[LevelField<V>]) -> V {
+    fields
+        .iter()
+        .fold(V::zero(), |mask, field| mask.or(field.mask()))
+}
+
+/// Returns the OR of two values, except that each level field holds the higher of the two
+/// levels rather than the OR of their bit patterns.
+pub(crate) fn union_levels<V: PermissionValue>(fields: &[LevelField<V>], a: &V, b: &V) -> V {
+    combine_levels(fields, a, b, a.or(b), |x, y| Some(x.max(y)))
+}
+
+/// Returns the AND of two values, except that each level field holds the lower of the two
+/// levels.
+pub(crate) fn intersect_levels<V: PermissionValue>(fields: &[LevelField<V>], a: &V, b: &V) -> V {
+    combine_levels(fields, a, b, a.and(b), |x, y| Some(x.min(y)))
+}
+
+/// Returns the bits of `a` that are not in `b`, except that each level field keeps the
+/// level of `a` if it is higher than the level of `b` and is cleared otherwise.
+pub(crate) fn subtract_levels<V: PermissionValue>(fields: &[LevelField<V>], a: &V, b: &V) -> V {
+    combine_levels(fields, a, b, a.and_not(b), |x, y| (x > y).then_some(x))
+}
+
+/// Returns `true` if `b` holds every flag of `a` and, in each level field, at least the
+/// level of `a`.
+pub(crate) fn levels_within<V: PermissionValue>(fields: &[LevelField<V>], a: &V, b: &V) -> bool {
+    let mask = field_mask(fields);
+    b.and_not(&mask).contains_all(&a.and_not(&mask))
+        && fields
+            .iter()
+            .all(|field| match (field.index_of(a), field.index_of(b)) {
+                (Some(x), Some(y)) => x <= y,
+                _ => b.and(field.mask()).contains_all(&a.and(field.mask())),
+            })
+}
+
+/// Replaces each level field of `combined` with the level `pick` chooses from the levels
+/// of `a` and `b`, or with no bits if it chooses none. Fields holding a bit pattern that
+/// is not a level keep the bits of `combined`.
+fn combine_levels<V: PermissionValue>(
+    fields: &[LevelField<V>],
+    a: &V,
+    b: &V,
+    combined: V,
+    pick: impl Fn(usize, usize) -> Option<usize>,
+) -> V {
+    fields.iter().fold(combined, |value, field| {
+        let (Some(x), Some(y)) = (field.index_of(a), field.index_of(b)) else {
+            return value;
+        };
+        let level = pick(x, y)
+            .and_then(|index| field.level_value(index))
+            .unwrap_or_else(V::zero);
+        value.and_not(field.mask()).or(&level)
+    })
+}
+
+impl<V: PermissionValue> RoleCapability<V> {
+    /// Returns the level the role holds in the named field, or `None` if the field does
+    /// not exist or its bit pattern is not a level.
+    pub fn level(&self, field: &str) -> Option<&CapabilityName> {
+        self.descriptor()
+            .level_field(field)?
+            .decode(&self.hex_value)
+    }
+
+    /// Sets the named field to a level, leaving every other bit unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnknownCapabilityError`] naming `Field=level` if the field or the level
+    /// does not exist.
+    pub fn set_level(&mut self, field: &str, level: &str) -> Result<(), UnknownCapabilityError> {
+        let unknown = || UnknownCapabilityError {
+            names: vec![format!("{}={}", field, level)],
+        };
+        let level_field = self.descriptor().level_field(field).ok_or_else(unknown)?;
+        let value = level_field.encode(level).ok_or_else(unknown)?;
+        self.hex_value = self.hex_value.and_not(level_field.mask()).or(&value);
+        Ok(())
+    }
+
+    /// Returns the `Field=level` names of the fields set to a non-zero level, with the
+    /// lowest bit of each field.
+    pub(crate) fn level_names(&self) -> Vec<(u32, CapabilityName)> {
+        self.descriptor()
+            .level_fields()
+            .iter()
+            .filter_map(|field| {
+                let name = level_name(field, &self.hex_value)?;
+                let bit = field.mask().bit_positions().first().copied()?;
+                Some((bit, name))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::{validate, ValidationError, ValidationRule};
+    use crate::models::{CapabilityDescriptor, CapabilityDescriptorBuilder, DescriptorError};
+    use crate::role_capability::CapabilityOrder;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .capability("Write", 0x8)
+            .level_field("Support", 0x30, ["none", "agent", "lead"])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_encode_and_decode_levels() {
+        let descriptor = create_test_descriptor();
+        let field = descriptor.level_field("FileAccess").unwrap();
+
+        assert_eq!(field.mask(), &0x6);
+        assert_eq!(field.levels().len(), 4);
+        assert_eq!(field.encode("none"), Some(0x0));
+        assert_eq!(field.encode("read"), Some(0x2));
+        assert_eq!(field.encode("write"), Some(0x4));
+        assert_eq!(field.encode("admin"), Some(0x6));
+        assert_eq!(field.encode("owner"), None);
+        assert_eq!(field.level_value(4), None);
+
+        assert_eq!(field.decode(&0x5).map(String::as_str), Some("write"));
+        assert_eq!(field.decode(&0x0).map(String::as_str), Some("none"));
+
+        let support = descriptor.level_field("Support").unwrap();
+        assert_eq!(support.decode(&0x30), None);
+    }
+
+    #[test]
+    fn test_non_contiguous_mask() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Middle", 0x2)
+            .level_field("Split", 0x5, ["low", "mid", "high", "top"])
+            .build()
+            .unwrap();
+        let field = descriptor.level_field("Split").unwrap();
+
+        assert_eq!(field.encode("mid"), Some(0x1));
+        assert_eq!(field.encode("high"), Some(0x4));
+        assert_eq!(field.decode(&0x7).map(String::as_str), Some("top"));
+    }
+
+    #[test]
+    fn test_validation_rejects_patterns_that_are_not_levels() {
+        let descriptor = create_test_descriptor();
+
+        assert!(validate(&0x3F, &descriptor).is_err());
+        assert!(validate(&0x2F, &descriptor).is_ok());
+
+        let error = validate(&0x31, &descriptor).unwrap_err();
+        assert_eq!(
+            error,
+            ValidationError::InvalidLevel {
+                field: "Support".to_string(),
+                bits: vec![4, 5],
+            }
+        );
+        assert_eq!(error.rule(), ValidationRule::InvalidLevel);
+        assert_eq!(
+            error.to_string(),
+            "invalid level: bit(s) 4, 5 of field \"Support\" do not match a level"
+        );
+
+        // Bits outside every capability and field are still unknown
+        assert_eq!(
+            validate(&0x40, &descriptor).unwrap_err().rule(),
+            ValidationRule::UnknownBits
+        );
+    }
+
+    #[test]
+    fn test_role_levels_alongside_flags() {
+        let descriptor = create_test_descriptor();
+
+        let mut role =
+            RoleCapability::from_names(descriptor.clone(), ["Write", "FileAccess=admin"]).unwrap();
+        assert_eq!(role.hex_value, 0xE);
+        assert_eq!(role.level("FileAccess").map(String::as_str), Some("admin"));
+        assert_eq!(role.level("Support").map(String::as_str), Some("none"));
+        assert_eq!(role.level("Missing"), None);
+
+        role.set_level("Support", "lead").unwrap();
+        role.set_level("FileAccess", "read").unwrap();
+        assert_eq!(role.hex_value, 0x2A);
+        assert_eq!(
+            role.to_name_list(CapabilityOrder::BitPosition),
+            vec!["FileAccess=read", "Write", "Support=lead"]
+        );
+        assert!(role.to_name_set().contains("Support=lead"));
+        assert!(role.has_capability(&"FileAccess=read".to_string()));
+        assert!(!role.has_capability(&"FileAccess=write".to_string()));
+
+        let parsed = crate::parse::parse_value("Write | Support=agent", &descriptor).unwrap();
+        assert_eq!(parsed, 0x18);
+
+        // Naming a field twice keeps the higher level
+        let parsed =
+            crate::parse::parse_value("FileAccess=read | FileAccess=write", &descriptor).unwrap();
+        assert_eq!(parsed, 0x4);
+        assert_eq!(
+            RoleCapability::encode_names(&descriptor, ["FileAccess=write", "FileAccess=read"]),
+            Ok(0x4)
+        );
+
+        let error = role.set_level("FileAccess", "owner").unwrap_err();
+        assert_eq!(error.names, vec!["FileAccess=owner"]);
+        assert!(RoleCapability::encode_names(&descriptor, ["Support=owner"]).is_err());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_fields() {
+        let cases: Vec<(CapabilityDescriptorBuilder, DescriptorError)> = vec![
+            (
+                CapabilityDescriptor::builder()
+                    .capability("Read", 0x1)
+                    .level_field("Access", 0x3, ["none", "read"]),
+                DescriptorError::BitInUse {
+                    name: "Access".to_string(),
+                    existing: "Read".to_string(),
+                    bit: 0,
+                },
+            ),
+            (
+                CapabilityDescriptor::builder().level_field(
+                    "Access",
+                    0x3,
+                    ["none", "read", "write", "admin", "owner"],
+                ),
+                DescriptorError::InvalidLevelCount {
+                    name: "Access".to_string(),
+                    count: 5,
+                    bits: 2,
+                },
+            ),
+            (
+                CapabilityDescriptor::builder().level_field("Access", 0x3, ["none", "none"]),
+                DescriptorError::DuplicateName {
+                    name: "Access=none".to_string(),
+                },
+            ),
+            (
+                CapabilityDescriptor::builder().level_field("Access", 0x0, ["none", "read"]),
+                DescriptorError::ZeroValue {
+                    name: "Access".to_string(),
+                },
+            ),
+            (
+                CapabilityDescriptor::builder()
+                    .level_field("Access", 0x3, ["none", "read"])
+                    .capability("Access", 0x4),
+                DescriptorError::DuplicateName {
+                    name: "Access".to_string(),
+                },
+            ),
+        ];
+
+        for (builder, expected) in cases {
+            assert_eq!(builder.build().unwrap_err(), expected);
+        }
+    }
+}
@@ -21,10 +21,12 @@
 //! };
 //!
 //! // Create a capability descriptor
-//! let mut descriptor = CapabilityDescriptor::new();
-//! descriptor.insert("Administrator".to_string(), 0x1);
-//! descriptor.insert("ManageServer".to_string(), 0x2);
-//! descriptor.insert("ManageRoles".to_string(), 0x4);
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Administrator", 0x1)
+//!     .capability("ManageServer", 0x2)
+//!     .capability("ManageRoles", 0x4)
+//!     .build()
+//!     .unwrap();
 //!
 //! // Create a role with combined permissions
 //! let permission_value: CapilityHexValue = 0x3; // Administrator + ManageServer
@@ -55,12 +57,48 @@
 //! ## Modules
 //!
 //! - [`models`]: Core type definitions and data structures
+//! - [`descriptor`]: The validated [`models::CapabilityDescriptor`] and its builder
 //! - [`checks`]: Validation functions for permission values and descriptors
+//! - [`format`]: Zero-padded hex, binary and octal rendering of permission values
+//! - [`level`]: Multi-bit fields holding named levels, such as `FileAccess=write`
+//! - [`lint`]: Descriptor linter reporting every integrity problem with a severity
+//! - [`parse`]: Parsing of hex, decimal, binary and `Name|Name` strings into checked values
+//! - [`registry`]: Named descriptors for several domains, resolving names like `channel:SendMessages`
+//! - [`requirement`]: Requirement expressions such as `Admin OR (Read AND Write)`, with failure reports
 //! - [`role_capability`]: Main struct for working with role permissions
+//! - [`algebra`]: Union, intersection, difference and complement of roles, with operators
+//! - [`diff`]: Added, removed and unchanged capabilities between two values
+//! - [`effective`]: Effective permissions of a member from a base role and assigned roles
+//! - [`explain`]: Decision traces explaining why a member has or lacks a capability
+//! - [`exclusive`]: Detection of mutually exclusive capabilities held together
+//! - [`migration`]: Versioned descriptors with renames, bit moves and removals, and value migration
+//! - [`namespace`]: Dotted namespaces such as `files.read` and `files.*` wildcards
+//! - [`normalize`]: Removal of capabilities whose prerequisites are missing, with an explanation
+//! - [`overwrite`]: Allow and deny overwrites for scoped resources, applied in layers
+//! - [`value`]: The [`value::PermissionValue`] trait for `i32`, `u32`, `u64` and `u128` values
+//! - [`bitset`]: A growable [`bitset::CapabilityBitSet`] value for systems beyond 128 capabilities
 
+pub mod algebra;
+pub mod bitset;
 pub mod checks;
+pub mod descriptor;
+pub mod diff;
+pub mod effective;
+pub mod exclusive;
+pub mod explain;
+pub mod format;
+pub mod level;
+pub mod lint;
+pub mod migration;
 pub mod models;
+pub mod namespace;
+pub mod normalize;
+pub mod overwrite;
+pub mod parse;
+pub mod registry;
+pub mod requirement;
 pub mod role_capability;
+pub mod value;
 
 // Re-export WASM-compatible types when wasm feature is enabled
 #[cfg(feature = "wasm")]
@@ -70,4 +108,6 @@ pub use checks::{
 #[cfg(feature = "wasm")]
 pub use models::JsCapabilityDescriptor;
 #[cfg(feature = "wasm")]
+pub use parse::js_parse_value;
+#[cfg(feature = "wasm")]
 pub use role_capability::JsRoleCapability;
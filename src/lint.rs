@@ -0,0 +1,549 @@
+//! # Lint Module
+//!
+//! This module inspects a capability descriptor for integrity problems and reports every
+//! finding at once, rather than stopping at the first failed rule like
+//! [`validate`](crate::checks::validate).
+//!
+//! ## Checks
+//!
+//! | Kind                               | Severity | Problem                                              |
+//! |------------------------------------|----------|------------------------------------------------------|
+//! | [`LintKind::ZeroValue`]            | Error    | An entry has the value `0` and can never be granted  |
+//! | [`LintKind::NegativeValue`]        | Error    | An entry is negative                                 |
+//! | [`LintKind::DuplicateValue`]       | Error    | Several names share exactly the same value           |
+//! | [`LintKind::ArithmeticOverflow`]   | Error    | The sum of all values overflows the value type       |
+//! | [`LintKind::OverlappingBits`]      | Warning  | Different values or level fields share some bits     |
+//! | [`LintKind::NonPowerOfTwo`]        | Warning  | An entry sets more than one bit                      |
+//! | [`LintKind::SignBit`]              | Warning  | An entry uses the sign bit of a signed type          |
+//! | [`LintKind::BitGap`]               | Info     | Bits below the highest used bit are unallocated      |
+//!
+//! The bits of level fields count as allocated.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     lint::{lint_descriptor, LintKind, Severity},
+//!     models::CapabilityMap,
+//! };
+//!
+//! let mut descriptor = CapabilityMap::new();
+//! descriptor.insert("Read".to_string(), 0x1);
+//! descriptor.insert("View".to_string(), 0x1);
+//! descriptor.insert("Admin".to_string(), 0x8);
+//!
+//! let report = lint_descriptor(&descriptor);
+//! assert!(report.has_errors());
+//! assert_eq!(report.max_severity(), Some(Severity::Error));
+//! assert!(report.findings().iter().any(|f| f.kind == LintKind::DuplicateValue));
+//! assert!(report.findings().iter().any(|f| f.kind == LintKind::BitGap && f.bits == vec![1, 2]));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::checks::checked_sum_hex_value_descriptor;
+use crate::descriptor::DescriptorLike;
+use crate::models::CapabilityName;
+use crate::value::PermissionValue;
+
+/// How serious a lint finding is.
+///
+/// Severities are ordered, so `Severity::Error > Severity::Warning > Severity::Info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth knowing, but not a problem on its own.
+    Info,
+    /// Likely a mistake, but the descriptor still validates values consistently.
+    Warning,
+    /// The descriptor is broken and will produce wrong results.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The kind of problem a lint finding reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintKind {
+    /// An entry has the value `0`.
+    ZeroValue,
+    /// An entry is negative.
+    NegativeValue,
+    /// Several names share exactly the same value.
+    DuplicateValue,
+    /// Summing the descriptor values overflows the value type.
+    ArithmeticOverflow,
+    /// Different values, or a level field and another value or field, share some bits.
+    OverlappingBits,
+    /// An entry sets more than one bit.
+    NonPowerOfTwo,
+    /// An entry uses the sign bit of a signed value type. The top bit of an unsigned type
+    /// is an ordinary bit and is not reported.
+    SignBit,
+    /// Bits below the highest allocated bit are not used by any entry.
+    BitGap,
+}
+
+impl LintKind {
+    /// Returns the severity assigned to this kind of finding.
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintKind::ZeroValue
+            | LintKind::NegativeValue
+            | LintKind::DuplicateValue
+            | LintKind::ArithmeticOverflow => Severity::Error,
+            LintKind::OverlappingBits | LintKind::NonPowerOfTwo | LintKind::SignBit => {
+                Severity::Warning
+            }
+            LintKind::BitGap => Severity::Info,
+        }
+    }
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LintKind::ZeroValue => "zero-value",
+            LintKind::NegativeValue => "negative-value",
+            LintKind::DuplicateValue => "duplicate-value",
+            LintKind::ArithmeticOverflow => "arithmetic-overflow",
+            LintKind::OverlappingBits => "overlapping-bits",
+            LintKind::NonPowerOfTwo => "non-power-of-two",
+            LintKind::SignBit => "sign-bit",
+            LintKind::BitGap => "bit-gap",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single problem found in a descriptor.
+///
+/// # Fields
+///
+/// * `severity` - How serious the problem is
+/// * `kind` - Which check produced the finding
+/// * `capabilities` - The capability names involved, sorted alphabetically (empty for descriptor-wide findings)
+/// * `bits` - The bit positions involved, in ascending order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub severity: Severity,
+    pub kind: LintKind,
+    pub capabilities: Vec<CapabilityName>,
+    pub bits: Vec<u32>,
+}
+
+impl LintFinding {
+    fn new(kind: LintKind, capabilities: Vec<CapabilityName>, bits: Vec<u32>) -> Self {
+        LintFinding {
+            severity: kind.severity(),
+            kind,
+            capabilities,
+            bits,
+        }
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self.capabilities.join(", ");
+        let bits = self
+            .bits
+            .iter()
+            .map(|bit| bit.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}[{}]: ", self.severity, self.kind)?;
+        match self.kind {
+            LintKind::ZeroValue => write!(f, "{} has the value 0 and can never be granted", names),
+            LintKind::NegativeValue => write!(f, "{} has a negative value", names),
+            LintKind::DuplicateValue => write!(f, "{} share the same value (bits {})", names, bits),
+            LintKind::ArithmeticOverflow => {
+                write!(f, "the sum of all descriptor values overflows")
+            }
+            LintKind::OverlappingBits => write!(f, "{} share bit(s) {}", names, bits),
+            LintKind::NonPowerOfTwo => write!(f, "{} sets multiple bits ({})", names, bits),
+            LintKind::SignBit => write!(f, "{} uses the sign bit {}", names, bits),
+            LintKind::BitGap => write!(f, "bit(s) {} are not allocated", bits),
+        }
+    }
+}
+
+/// The result of [`lint_descriptor`]: every finding, ordered by kind and then by capability name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintReport {
+    findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Returns all findings.
+    pub fn findings(&self) -> &[LintFinding] {
+        &self.findings
+    }
+
+    /// Returns `true` if there are no findings at all.
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns `true` if at least one finding has [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Returns the highest severity among the findings, or `None` for a clean report.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Returns the findings with at least the given severity.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &LintFinding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.severity >= severity)
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "no problems found");
+        }
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks a capability descriptor for integrity problems.
+///
+/// Unlike [`validate`](crate::checks::validate), which stops at the first failed rule,
+/// this function runs every check and collects all findings into a [`LintReport`].
+/// See the [module documentation](self) for the list of checks and their severities.
+///
+/// # Arguments
+///
+/// * `descriptor` - The capability descriptor to inspect, usually a raw
+///   [`CapabilityMap`](crate::models::CapabilityMap) that has not been validated yet
+///
+/// # Returns
+///
+/// A [`LintReport`] listing every finding. A report with no findings means the descriptor
+/// consists of distinct, contiguous, single-bit values.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{lint::lint_descriptor, models::CapabilityDescriptor};
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("Write", 0x2)
+///     .build()
+///     .unwrap();
+///
+/// assert!(lint_descriptor(&descriptor).is_clean());
+/// ```
+pub fn lint_descriptor<D: DescriptorLike>(descriptor: &D) -> LintReport {
+    let mut entries: Vec<(&CapabilityName, &D::Value)> =
+        descriptor.capability_map().iter().collect();
+    entries.sort();
+
+    let mut findings = Vec::new();
+    let mut by_value: BTreeMap<&D::Value, Vec<CapabilityName>> = BTreeMap::new();
+    let mut used = D::Value::zero();
+
+    for &(name, value) in &entries {
+        if value.is_zero() {
+            findings.push(LintFinding::new(
+                LintKind::ZeroValue,
+                vec![name.clone()],
+                Vec::new(),
+            ));
+            continue;
+        }
+        if value.is_negative() {
+            findings.push(LintFinding::new(
+                LintKind::NegativeValue,
+                vec![name.clone()],
+                vec![value.bit_len() - 1],
+            ));
+        }
+        if let Some(bits) = D::Value::BITS {
+            let is_sign_bit = D::Value::bit(bits - 1).is_some_and(|bit| bit.is_negative());
+            if is_sign_bit && value.test_bit(bits - 1) {
+                findings.push(LintFinding::new(
+                    LintKind::SignBit,
+                    vec![name.clone()],
+                    vec![bits - 1],
+                ));
+            }
+        }
+        if value.count_ones() > 1 {
+            findings.push(LintFinding::new(
+                LintKind::NonPowerOfTwo,
+                vec![name.clone()],
+                value.bit_positions(),
+            ));
+        }
+        by_value.entry(value).or_default().push(name.clone());
+        used = used.or(value);
+    }
+
+    for (value, names) in &by_value {
+        if names.len() > 1 {
+            findings.push(LintFinding::new(
+                LintKind::DuplicateValue,
+                names.clone(),
+                value.bit_positions(),
+            ));
+        }
+    }
+
+    let distinct: Vec<(&D::Value, &CapabilityName)> = by_value
+        .iter()
+        .map(|(value, names)| (*value, &names[0]))
+        .collect();
+    for (i, &(a, a_name)) in distinct.iter().enumerate() {
+        for &(b, b_name) in &distinct[i + 1..] {
+            let shared = a.and(b);
+            if !shared.is_zero() {
+                let mut names = vec![a_name.clone(), b_name.clone()];
+                names.sort();
+                findings.push(LintFinding::new(
+                    LintKind::OverlappingBits,
+                    names,
+                    shared.bit_positions(),
+                ));
+            }
+        }
+    }
+
+    // Level fields occupy their bits too, and must not share them with anything else
+    let fields = descriptor.level_fields();
+    for (i, field) in fields.iter().enumerate() {
+        let others = entries.iter().map(|&(name, value)| (name, value)).chain(
+            fields[i + 1..]
+                .iter()
+                .map(|other| (other.name(), other.mask())),
+        );
+        for (name, value) in others {
+            let shared = field.mask().and(value);
+            if !shared.is_zero() {
+                let mut names = vec![field.name().clone(), name.clone()];
+                names.sort();
+                findings.push(LintFinding::new(
+                    LintKind::OverlappingBits,
+                    names,
+                    shared.bit_positions(),
+                ));
+            }
+        }
+        used = used.or(field.mask());
+    }
+
+    if checked_sum_hex_value_descriptor(descriptor).is_none() {
+        findings.push(LintFinding::new(
+            LintKind::ArithmeticOverflow,
+            Vec::new(),
+            Vec::new(),
+        ));
+    }
+
+    let gaps: Vec<u32> = (0..used.bit_len())
+        .filter(|&bit| !used.test_bit(bit))
+        .collect();
+    if !gaps.is_empty() {
+        findings.push(LintFinding::new(LintKind::BitGap, Vec::new(), gaps));
+    }
+
+    findings.sort_by(|a, b| {
+        a.kind
+            .cmp(&b.kind)
+            .then_with(|| a.capabilities.cmp(&b.capabilities))
+    });
+    LintReport { findings }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::LevelField;
+    use crate::models::CapabilityMap;
+
+    fn create_test_descriptor() -> CapabilityMap {
+        let mut descriptor = CapabilityMap::new();
+        descriptor.insert("Read".to_string(), 0x1);
+        descriptor.insert("Write".to_string(), 0x2);
+        descriptor.insert("Execute".to_string(), 0x4);
+        descriptor.insert("Admin".to_string(), 0x8);
+        descriptor
+    }
+
+    fn kinds(report: &LintReport) -> Vec<LintKind> {
+        report
+            .findings()
+            .iter()
+            .map(|finding| finding.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_descriptor() {
+        let report = lint_descriptor(&create_test_descriptor());
+        assert!(report.is_clean());
+        assert!(!report.has_errors());
+        assert_eq!(report.max_severity(), None);
+        assert_eq!(report.to_string(), "no problems found");
+    }
+
+    #[test]
+    fn test_zero_and_negative_values() {
+        let mut descriptor = create_test_descriptor();
+        descriptor.insert("Nothing".to_string(), 0x0);
+        descriptor.insert("Everything".to_string(), -1);
+
+        let report = lint_descriptor(&descriptor);
+        assert!(report.has_errors());
+
+        let zero = &report.findings()[0];
+        assert_eq!(zero.kind, LintKind::ZeroValue);
+        assert_eq!(zero.capabilities, vec!["Nothing".to_string()]);
+
+        let negative = &report.findings()[1];
+        assert_eq!(negative.kind, LintKind::NegativeValue);
+        assert_eq!(negative.capabilities, vec!["Everything".to_string()]);
+        assert_eq!(negative.bits, vec![31]);
+
+        assert!(kinds(&report).contains(&LintKind::SignBit));
+    }
+
+    #[test]
+    fn test_duplicate_values() {
+        let mut descriptor = create_test_descriptor();
+        descriptor.insert("View".to_string(), 0x1);
+
+        let report = lint_descriptor(&descriptor);
+        assert_eq!(kinds(&report), vec![LintKind::DuplicateValue]);
+
+        let finding = &report.findings()[0];
+        assert_eq!(finding.severity, Severity::Error);
+        assert_eq!(finding.capabilities, vec!["Read", "View"]);
+        assert_eq!(finding.bits, vec![0]);
+        assert_eq!(
+            finding.to_string(),
+            "error[duplicate-value]: Read, View share the same value (bits 0)"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_and_multi_bit_values() {
+        let mut descriptor = create_test_descriptor();
+        descriptor.insert("ReadWrite".to_string(), 0x3);
+
+        let report = lint_descriptor(&descriptor);
+        assert_eq!(
+            kinds(&report),
+            vec![
+                LintKind::OverlappingBits,
+                LintKind::OverlappingBits,
+                LintKind::NonPowerOfTwo
+            ]
+        );
+        assert_eq!(report.max_severity(), Some(Severity::Warning));
+
+        let overlap = &report.findings()[0];
+        assert_eq!(overlap.capabilities, vec!["Read", "ReadWrite"]);
+        assert_eq!(overlap.bits, vec![0]);
+        assert_eq!(report.findings()[2].bits, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_bit_gaps() {
+        let mut descriptor = CapabilityMap::new();
+        descriptor.insert("Low".to_string(), 0x1);
+        descriptor.insert("High".to_string(), 0x20);
+
+        let report = lint_descriptor(&descriptor);
+        assert_eq!(kinds(&report), vec![LintKind::BitGap]);
+        assert_eq!(report.findings()[0].bits, vec![1, 2, 3, 4]);
+        assert_eq!(report.max_severity(), Some(Severity::Info));
+        assert_eq!(report.at_least(Severity::Warning).count(), 0);
+    }
+
+    #[test]
+    fn test_arithmetic_overflow() {
+        let mut descriptor: CapabilityMap<u64> = CapabilityMap::new();
+        descriptor.insert("All".to_string(), u64::MAX);
+        descriptor.insert("One".to_string(), 0x1);
+
+        let report = lint_descriptor(&descriptor);
+        assert!(kinds(&report).contains(&LintKind::ArithmeticOverflow));
+        // The top bit of an unsigned type is not a sign bit
+        assert!(!kinds(&report).contains(&LintKind::SignBit));
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_level_fields() {
+        let descriptor = crate::models::CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .capability("Admin", 0x8)
+            .build()
+            .unwrap();
+        assert!(lint_descriptor(&descriptor).is_clean());
+
+        // A field sharing bits with a capability, as a hand-made descriptor might
+        struct WithField(CapabilityMap, Vec<LevelField<i32>>);
+        impl DescriptorLike for WithField {
+            type Value = i32;
+
+            fn capability_map(&self) -> &CapabilityMap {
+                &self.0
+            }
+
+            fn level_fields(&self) -> &[LevelField<i32>] {
+                &self.1
+            }
+        }
+        let field = LevelField::new(
+            "Access".to_string(),
+            0x30,
+            vec!["none".to_string(), "read".to_string()],
+        );
+        let mut map = create_test_descriptor();
+        map.insert("Share".to_string(), 0x10);
+
+        let report = lint_descriptor(&WithField(map, vec![field]));
+        assert_eq!(kinds(&report), vec![LintKind::OverlappingBits]);
+        assert_eq!(report.findings()[0].capabilities, vec!["Access", "Share"]);
+        assert_eq!(report.findings()[0].bits, vec![4]);
+    }
+
+    #[test]
+    fn test_report_display_lists_every_finding() {
+        let mut descriptor = CapabilityMap::new();
+        descriptor.insert("Nothing".to_string(), 0x0);
+        descriptor.insert("High".to_string(), 0x4);
+
+        let report = lint_descriptor(&descriptor);
+        assert_eq!(
+            report.to_string(),
+            "error[zero-value]: Nothing has the value 0 and can never be granted\n\
+             info[bit-gap]: bit(s) 0, 1 are not allocated"
+        );
+    }
+}
@@ -0,0 +1,700 @@
+//! # Migration Module
+//!
+//! This module remaps stored permission values when capabilities are renumbered, renamed or
+//! retired. A [`DescriptorHistory`] holds every version of a descriptor together with the
+//! changes declared between consecutive versions:
+//!
+//! - A **rename** gives a capability or level field a new name in the next version
+//! - A **move** is declared by giving a capability a different bit in the next version
+//! - A **removal** retires a capability or level field
+//!
+//! A capability that disappears without a rename or removal is rejected when the history
+//! is built. [`DescriptorHistory::migrate`] translates a value from one version to another,
+//! in either direction, and reports every bit it could not map.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     migration::{DescriptorHistory, UnmappedReason},
+//!     models::CapabilityDescriptor,
+//! };
+//!
+//! let v1 = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Speak", 0x2)
+//!     .capability("Legacy", 0x4)
+//!     .build()
+//!     .unwrap();
+//! let v2 = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("SendMessages", 0x8)
+//!     .build()
+//!     .unwrap();
+//!
+//! let history = DescriptorHistory::builder(1, v1)
+//!     .version(2, v2)
+//!     .rename("Speak", "SendMessages")
+//!     .remove("Legacy")
+//!     .build()
+//!     .unwrap();
+//!
+//! let migration = history.migrate(&0x7, 1, 2).unwrap();
+//! assert_eq!(migration.value, 0x9);
+//! assert_eq!(migration.unmapped.len(), 1);
+//! assert_eq!(migration.unmapped[0].reason, UnmappedReason::Removed { version: 2 });
+//! assert_eq!(migration.to_string(), "bit 2 (Legacy) of version 1: removed in version 2\n");
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::models::{CapabilityDescriptor, CapabilityName, CapilityHexValue};
+use crate::value::PermissionValue;
+
+/// Error returned when a history is rejected or a migration cannot run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// A version number is not greater than the one before it.
+    NonIncreasingVersion { version: u32 },
+    /// A rename or removal was declared before a second version was added.
+    NoPreviousVersion { version: u32 },
+    /// A rename or removal refers to a name that its version does not define.
+    UnknownCapability { version: u32, name: CapabilityName },
+    /// A capability of the previous version is missing without a rename or removal.
+    UndeclaredRemoval { version: u32, name: CapabilityName },
+    /// Two capabilities of the previous version map to the same name.
+    ConflictingRename { version: u32, name: CapabilityName },
+    /// The history has no such version.
+    UnknownVersion { version: u32 },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::NonIncreasingVersion { version } => write!(
+                f,
+                "version {} is not greater than the previous version",
+                version
+            ),
+            HistoryError::NoPreviousVersion { version } => write!(
+                f,
+                "version {} is the first version and has no changes",
+                version
+            ),
+            HistoryError::UnknownCapability { version, name } => write!(
+                f,
+                "version {} refers to undefined capability {:?}",
+                version, name
+            ),
+            HistoryError::UndeclaredRemoval { version, name } => write!(
+                f,
+                "capability {:?} is missing from version {} without a rename or removal",
+                name, version
+            ),
+            HistoryError::ConflictingRename { version, name } => write!(
+                f,
+                "several capabilities map to {:?} in version {}",
+                name, version
+            ),
+            HistoryError::UnknownVersion { version } => write!(f, "unknown version {}", version),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+/// Why a bit could not be mapped by [`DescriptorHistory::migrate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum UnmappedReason {
+    /// The bit is not defined in its version.
+    Undefined,
+    /// The capability was removed in the given version.
+    Removed { version: u32 },
+    /// The capability does not exist in the given, earlier version.
+    Missing { version: u32 },
+}
+
+/// A bit that [`DescriptorHistory::migrate`] dropped from the value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnmappedBit {
+    /// The version the bit was set in when it was dropped.
+    pub version: u32,
+    /// The bit position in that version.
+    pub bit: u32,
+    /// The capability or level field the bit belonged to, if any.
+    pub capability: Option<CapabilityName>,
+    /// Why the bit was dropped.
+    pub reason: UnmappedReason,
+}
+
+impl fmt::Display for UnmappedBit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bit {}", self.bit)?;
+        if let Some(capability) = &self.capability {
+            write!(f, " ({})", capability)?;
+        }
+        write!(f, " of version {}: ", self.version)?;
+        match self.reason {
+            UnmappedReason::Undefined => write!(f, "not defined"),
+            UnmappedReason::Removed { version } => write!(f, "removed in version {}", version),
+            UnmappedReason::Missing { version } => {
+                write!(f, "not defined in version {}", version)
+            }
+        }
+    }
+}
+
+/// The result of [`DescriptorHistory::migrate`]: the translated value and the bits that
+/// could not be mapped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration<V = CapilityHexValue> {
+    /// The version the value was migrated from.
+    pub from: u32,
+    /// The version the value was migrated to.
+    pub to: u32,
+    /// The value in the target version.
+    pub value: V,
+    /// The dropped bits, in the order they were dropped.
+    pub unmapped: Vec<UnmappedBit>,
+}
+
+impl<V> Migration<V> {
+    /// Returns `true` if every set bit was mapped.
+    pub fn is_complete(&self) -> bool {
+        self.unmapped.is_empty()
+    }
+}
+
+/// Renders one line per dropped bit, such as
+/// `bit 2 (Legacy) of version 1: removed in version 2`.
+impl<V> fmt::Display for Migration<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for unmapped in &self.unmapped {
+            writeln!(f, "{}", unmapped)?;
+        }
+        Ok(())
+    }
+}
+
+/// The renames and removals declared between a version and the one before it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct VersionChanges {
+    renames: BTreeMap<CapabilityName, CapabilityName>,
+    removals: BTreeSet<CapabilityName>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version<V> {
+    number: u32,
+    descriptor: CapabilityDescriptor<V>,
+    changes: VersionChanges,
+}
+
+/// Every version of a descriptor and the changes between consecutive versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorHistory<V = CapilityHexValue> {
+    versions: Vec<Version<V>>,
+}
+
+impl<V: PermissionValue> DescriptorHistory<V> {
+    /// Creates a builder starting from the first version.
+    pub fn builder(
+        version: u32,
+        descriptor: CapabilityDescriptor<V>,
+    ) -> DescriptorHistoryBuilder<V> {
+        DescriptorHistoryBuilder::new(version, descriptor)
+    }
+
+    /// Returns the descriptor of a version, if it exists.
+    pub fn get(&self, version: u32) -> Option<&CapabilityDescriptor<V>> {
+        self.index_of(version)
+            .map(|index| &self.versions[index].descriptor)
+    }
+
+    /// Returns the version numbers, oldest first.
+    pub fn versions(&self) -> Vec<u32> {
+        self.versions.iter().map(|version| version.number).collect()
+    }
+
+    /// Returns the latest version number.
+    pub fn latest(&self) -> u32 {
+        self.versions[self.versions.len() - 1].number
+    }
+
+    /// Translates a value from one version to another, in either direction.
+    ///
+    /// The value passes through every version in between. Each capability keeps its bits
+    /// under its new name, and each level field keeps its level by name. Bits that are not
+    /// defined, belong to a removed capability or have no counterpart in an older version
+    /// are dropped and reported in [`Migration::unmapped`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::UnknownVersion`] if either version is not in the history.
+    pub fn migrate(&self, value: &V, from: u32, to: u32) -> Result<Migration<V>, HistoryError> {
+        let unknown = |version| HistoryError::UnknownVersion { version };
+        let start = self.index_of(from).ok_or_else(|| unknown(from))?;
+        let end = self.index_of(to).ok_or_else(|| unknown(to))?;
+
+        let mut value = value.clone();
+        let mut unmapped = Vec::new();
+        let mut index = start;
+        while index != end {
+            let next = if end > index { index + 1 } else { index - 1 };
+            let (source, target) = (&self.versions[index], &self.versions[next]);
+            let rename = |name: &str| -> Result<String, UnmappedReason> {
+                if next > index {
+                    let changes = &target.changes;
+                    if changes.removals.contains(name) {
+                        return Err(UnmappedReason::Removed {
+                            version: target.number,
+                        });
+                    }
+                    Ok(changes
+                        .renames
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(|| name.to_string()))
+                } else {
+                    let renamed = source
+                        .changes
+                        .renames
+                        .iter()
+                        .find(|(_, new)| *new == name)
+                        .map(|(old, _)| old.clone());
+                    Ok(renamed.unwrap_or_else(|| name.to_string()))
+                }
+            };
+            value = migrate_step(&value, source, target, rename, &mut unmapped);
+            index = next;
+        }
+
+        Ok(Migration {
+            from,
+            to,
+            value,
+            unmapped,
+        })
+    }
+
+    fn index_of(&self, version: u32) -> Option<usize> {
+        self.versions
+            .iter()
+            .position(|candidate| candidate.number == version)
+    }
+}
+
+fn migrate_step<V, F>(
+    value: &V,
+    source: &Version<V>,
+    target: &Version<V>,
+    rename: F,
+    unmapped: &mut Vec<UnmappedBit>,
+) -> V
+where
+    V: PermissionValue,
+    F: Fn(&str) -> Result<String, UnmappedReason>,
+{
+    let missing = UnmappedReason::Missing {
+        version: target.number,
+    };
+    let mut migrated = V::zero();
+    let mut covered = V::zero();
+    let mut drop_bits = |bits: &V, capability: Option<&CapabilityName>, reason| {
+        for bit in bits.bit_positions() {
+            unmapped.push(UnmappedBit {
+                version: source.number,
+                bit,
+                capability: capability.cloned(),
+                reason,
+            });
+        }
+    };
+
+    for (name, bit) in source.descriptor.sorted_entries() {
+        covered = covered.or(bit);
+        let held = value.and(bit);
+        if held.is_zero() {
+            continue;
+        }
+        let mapped = rename(name)
+            .and_then(|new_name| target.descriptor.get(&new_name).cloned().ok_or(missing));
+        match mapped {
+            Ok(new_bit) => migrated = migrated.or(&new_bit),
+            Err(reason) => drop_bits(&held, Some(name), reason),
+        }
+    }
+
+    for field in source.descriptor.level_fields() {
+        covered = covered.or(field.mask());
+        let held = value.and(field.mask());
+        if held.is_zero() {
+            continue;
+        }
+        let Some(level) = field.decode(value) else {
+            drop_bits(&held, Some(field.name()), UnmappedReason::Undefined);
+            continue;
+        };
+        let mapped = rename(field.name()).and_then(|new_name| {
+            target
+                .descriptor
+                .level_field(&new_name)
+                .and_then(|new_field| new_field.encode(level))
+                .ok_or(missing)
+        });
+        match mapped {
+            Ok(new_value) => migrated = migrated.or(&new_value),
+            Err(reason) => drop_bits(&held, Some(field.name()), reason),
+        }
+    }
+
+    drop_bits(&value.and_not(&covered), None, UnmappedReason::Undefined);
+    migrated
+}
+
+/// Builder for [`DescriptorHistory`].
+///
+/// [`rename`](DescriptorHistoryBuilder::rename) and
+/// [`remove`](DescriptorHistoryBuilder::remove) declare changes between the latest added
+/// version and the one before it. Changes are checked by
+/// [`build`](DescriptorHistoryBuilder::build), and the first rejected step is reported.
+#[derive(Debug, Clone)]
+pub struct DescriptorHistoryBuilder<V = CapilityHexValue> {
+    versions: Vec<Version<V>>,
+    error: Option<HistoryError>,
+}
+
+impl<V: PermissionValue> DescriptorHistoryBuilder<V> {
+    /// Creates a builder starting from the first version.
+    pub fn new(version: u32, descriptor: CapabilityDescriptor<V>) -> Self {
+        DescriptorHistoryBuilder {
+            versions: vec![Version {
+                number: version,
+                descriptor,
+                changes: VersionChanges::default(),
+            }],
+            error: None,
+        }
+    }
+
+    /// Adds the next version. Its number must be greater than the previous one.
+    pub fn version(mut self, version: u32, descriptor: CapabilityDescriptor<V>) -> Self {
+        if self.error.is_none() && version <= self.latest().number {
+            self.error = Some(HistoryError::NonIncreasingVersion { version });
+        }
+        self.versions.push(Version {
+            number: version,
+            descriptor,
+            changes: VersionChanges::default(),
+        });
+        self
+    }
+
+    /// Declares that a capability or level field of the previous version is named `new`
+    /// in the latest version.
+    pub fn rename(
+        mut self,
+        old: impl Into<CapabilityName>,
+        new: impl Into<CapabilityName>,
+    ) -> Self {
+        if self.check_has_previous() {
+            let (old, new) = (old.into(), new.into());
+            self.latest_mut().changes.renames.insert(old, new);
+        }
+        self
+    }
+
+    /// Declares that a capability or level field of the previous version no longer exists
+    /// in the latest version.
+    pub fn remove(mut self, name: impl Into<CapabilityName>) -> Self {
+        if self.check_has_previous() {
+            let name = name.into();
+            self.latest_mut().changes.removals.insert(name);
+        }
+        self
+    }
+
+    /// Builds the history.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HistoryError`] if version numbers do not increase, or if a rename or
+    /// removal refers to an undefined name, a capability disappears without one, or two
+    /// capabilities map to the same name.
+    pub fn build(self) -> Result<DescriptorHistory<V>, HistoryError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        for pair in self.versions.windows(2) {
+            check_changes(&pair[0].descriptor, &pair[1])?;
+        }
+        Ok(DescriptorHistory {
+            versions: self.versions,
+        })
+    }
+
+    fn latest(&self) -> &Version<V> {
+        &self.versions[self.versions.len() - 1]
+    }
+
+    fn latest_mut(&mut self) -> &mut Version<V> {
+        let last = self.versions.len() - 1;
+        &mut self.versions[last]
+    }
+
+    fn check_has_previous(&mut self) -> bool {
+        if self.error.is_none() && self.versions.len() < 2 {
+            self.error = Some(HistoryError::NoPreviousVersion {
+                version: self.latest().number,
+            });
+        }
+        self.error.is_none()
+    }
+}
+
+fn defined_names<V: PermissionValue>(descriptor: &CapabilityDescriptor<V>) -> BTreeSet<&str> {
+    descriptor
+        .names()
+        .map(String::as_str)
+        .chain(
+            descriptor
+                .level_fields()
+                .iter()
+                .map(|field| field.name().as_str()),
+        )
+        .collect()
+}
+
+fn check_changes<V: PermissionValue>(
+    previous: &CapabilityDescriptor<V>,
+    current: &Version<V>,
+) -> Result<(), HistoryError> {
+    let version = current.number;
+    let old_names = defined_names(previous);
+    let new_names = defined_names(&current.descriptor);
+    let unknown = |name: &str| HistoryError::UnknownCapability {
+        version,
+        name: name.to_string(),
+    };
+
+    for name in current
+        .changes
+        .renames
+        .keys()
+        .chain(&current.changes.removals)
+    {
+        if !old_names.contains(name.as_str()) {
+            return Err(unknown(name));
+        }
+    }
+
+    let mut targets = BTreeSet::new();
+    for name in old_names {
+        if current.changes.removals.contains(name) {
+            continue;
+        }
+        let target = current
+            .changes
+            .renames
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or(name);
+        if !new_names.contains(target) {
+            return Err(if current.changes.renames.contains_key(name) {
+                unknown(target)
+            } else {
+                HistoryError::UndeclaredRemoval {
+                    version,
+                    name: name.to_string(),
+                }
+            });
+        }
+        if !targets.insert(target) {
+            return Err(HistoryError::ConflictingRename {
+                version,
+                name: target.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_history() -> DescriptorHistory {
+        let v1 = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Speak", 0x2)
+            .capability("Legacy", 0x4)
+            .level_field("Files", 0x18, ["none", "read", "write"])
+            .build()
+            .unwrap();
+        let v2 = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("SendMessages", 0x2)
+            .level_field("FileAccess", 0x60, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+        let v3 = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("SendMessages", 0x80)
+            .capability("Pin", 0x2)
+            .level_field("FileAccess", 0x60, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+
+        DescriptorHistory::builder(1, v1)
+            .version(2, v2)
+            .rename("Speak", "SendMessages")
+            .rename("Files", "FileAccess")
+            .remove("Legacy")
+            .version(3, v3)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_forward_migration() {
+        let history = create_test_history();
+        assert_eq!(history.versions(), vec![1, 2, 3]);
+        assert_eq!(history.latest(), 3);
+
+        // Read, Speak and Files=write
+        let migration = history.migrate(&0x13, 1, 3).unwrap();
+        assert_eq!(migration.value, 0xC1);
+        assert!(migration.is_complete());
+
+        let migration = history.migrate(&0x27, 1, 2).unwrap();
+        assert_eq!(migration.value, 0x3);
+        assert_eq!(
+            migration.unmapped,
+            vec![
+                UnmappedBit {
+                    version: 1,
+                    bit: 2,
+                    capability: Some("Legacy".to_string()),
+                    reason: UnmappedReason::Removed { version: 2 },
+                },
+                UnmappedBit {
+                    version: 1,
+                    bit: 5,
+                    capability: None,
+                    reason: UnmappedReason::Undefined,
+                },
+            ]
+        );
+        assert_eq!(
+            migration.to_string(),
+            "bit 2 (Legacy) of version 1: removed in version 2\n\
+             bit 5 of version 1: not defined\n"
+        );
+    }
+
+    #[test]
+    fn test_backward_migration() {
+        let history = create_test_history();
+
+        // Read, Pin, FileAccess=admin and SendMessages
+        let migration = history.migrate(&0xE3, 3, 1).unwrap();
+        assert_eq!(migration.value, 0x3);
+        assert_eq!(
+            migration.unmapped,
+            vec![
+                UnmappedBit {
+                    version: 3,
+                    bit: 1,
+                    capability: Some("Pin".to_string()),
+                    reason: UnmappedReason::Missing { version: 2 },
+                },
+                UnmappedBit {
+                    version: 2,
+                    bit: 5,
+                    capability: Some("FileAccess".to_string()),
+                    reason: UnmappedReason::Missing { version: 1 },
+                },
+                UnmappedBit {
+                    version: 2,
+                    bit: 6,
+                    capability: Some("FileAccess".to_string()),
+                    reason: UnmappedReason::Missing { version: 1 },
+                },
+            ]
+        );
+
+        let same = history.migrate(&0x1, 2, 2).unwrap();
+        assert_eq!(same.value, 0x1);
+        assert_eq!(
+            history.migrate(&0x1, 1, 4),
+            Err(HistoryError::UnknownVersion { version: 4 })
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_histories() {
+        let descriptor = |names: &[&str]| -> CapabilityDescriptor {
+            names
+                .iter()
+                .fold(CapabilityDescriptor::builder(), |builder, name| {
+                    builder.next_capability(*name)
+                })
+                .build()
+                .unwrap()
+        };
+
+        let cases = [
+            (
+                DescriptorHistory::builder(2, descriptor(&["A"])).version(1, descriptor(&["A"])),
+                HistoryError::NonIncreasingVersion { version: 1 },
+            ),
+            (
+                DescriptorHistory::builder(1, descriptor(&["A"])).remove("A"),
+                HistoryError::NoPreviousVersion { version: 1 },
+            ),
+            (
+                DescriptorHistory::builder(1, descriptor(&["A", "B"]))
+                    .version(2, descriptor(&["A"])),
+                HistoryError::UndeclaredRemoval {
+                    version: 2,
+                    name: "B".to_string(),
+                },
+            ),
+            (
+                DescriptorHistory::builder(1, descriptor(&["A"]))
+                    .version(2, descriptor(&["A"]))
+                    .remove("Z"),
+                HistoryError::UnknownCapability {
+                    version: 2,
+                    name: "Z".to_string(),
+                },
+            ),
+            (
+                DescriptorHistory::builder(1, descriptor(&["A"]))
+                    .version(2, descriptor(&["B"]))
+                    .rename("A", "C"),
+                HistoryError::UnknownCapability {
+                    version: 2,
+                    name: "C".to_string(),
+                },
+            ),
+            (
+                DescriptorHistory::builder(1, descriptor(&["A", "B"]))
+                    .version(2, descriptor(&["B"]))
+                    .rename("A", "B"),
+                HistoryError::ConflictingRename {
+                    version: 2,
+                    name: "B".to_string(),
+                },
+            ),
+        ];
+
+        for (builder, expected) in cases {
+            assert_eq!(builder.build().unwrap_err(), expected);
+        }
+    }
+}
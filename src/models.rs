@@ -7,7 +7,8 @@
 //!
 //! The types in this module follow a hierarchical structure:
 //! - Individual capabilities are represented by names and hex values
-//! - Descriptors map capability names to their hex values
+//! - Descriptors map capability names to their hex values ([`CapabilityMap`] is the raw,
+//!   unvalidated form and [`CapabilityDescriptor`] the validated one)
 //! - Combined permission values represent multiple capabilities
 //! - Sets provide collections of capabilities in different formats
 
@@ -46,30 +47,32 @@ pub type CapabilityName = String;
 /// ```
 pub type CapabilityHexUnitValue = i32;
 
-/// A mapping between capability names and their corresponding hexadecimal values.
+/// An unvalidated mapping between capability names and their corresponding hexadecimal values.
 ///
-/// This descriptor defines the available capabilities in a permission system
-/// and their associated hex values. It serves as the authoritative source
-/// for validating and translating permission values.
-///
-/// # Design Notes
-///
-/// - Each capability should have a unique power-of-2 hex value
-/// - The descriptor is flexible and doesn't predefine specific capabilities
-/// - This allows the library to work with different permission systems
+/// This is the raw form of a descriptor: a plain `HashMap` that accepts any entry,
+/// including duplicate bits, zero or negative values. Convert it into a
+/// [`CapabilityDescriptor`] with `TryFrom` to validate it, or pass it directly to the
+/// [`checks`](crate::checks) and [`lint`](crate::lint) functions to inspect it.
 ///
 /// # Examples
 ///
 /// ```rust
-/// use permission_translation::models::{CapabilityDescriptor, CapabilityHexUnitValue};
+/// use permission_translation::models::{CapabilityDescriptor, CapabilityMap};
+///
+/// let mut map = CapabilityMap::new();
+/// map.insert("Administrator".to_string(), 0x1);
+/// map.insert("ManageServer".to_string(), 0x2);
 ///
-/// let mut descriptor = CapabilityDescriptor::new();
-/// descriptor.insert("Administrator".to_string(), 0x1);
-/// descriptor.insert("ManageServer".to_string(), 0x2);
-/// descriptor.insert("ManageRoles".to_string(), 0x4);
-/// descriptor.insert("CreateInvitation".to_string(), 0x8);
+/// let descriptor = CapabilityDescriptor::try_from(map).unwrap();
 /// ```
-pub type CapabilityDescriptor = HashMap<CapabilityName, CapabilityHexUnitValue>;
+///
+/// The value type defaults to [`CapabilityHexUnitValue`] but can be any
+/// [`PermissionValue`](crate::value::PermissionValue), such as `u64` or `u128`.
+pub type CapabilityMap<V = CapabilityHexUnitValue> = HashMap<CapabilityName, V>;
+
+pub use crate::descriptor::{
+    Bypass, CapabilityDescriptor, CapabilityDescriptorBuilder, DescriptorError,
+};
 
 /// A combined permission value representing multiple capabilities.
 ///
@@ -106,7 +109,7 @@ pub type CapilityHexValue = i32;
 /// hex_set.insert(0x2); // ManageServer
 /// hex_set.insert(0x4); // ManageRoles
 /// ```
-pub type CapabilityHexUnitSet = HashSet<CapabilityHexUnitValue>;
+pub type CapabilityHexUnitSet<V = CapabilityHexUnitValue> = HashSet<V>;
 
 /// A set of human-readable capability names extracted from a combined permission.
 ///
@@ -133,13 +136,20 @@ use wasm_bindgen::prelude::*;
 #[cfg(feature = "wasm")]
 use serde::{Deserialize, Serialize};
 
-/// WASM-compatible wrapper for CapabilityDescriptor
+/// WASM-compatible wrapper for a capability map, validated when a role is created from it
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct JsCapabilityDescriptor {
     #[wasm_bindgen(skip)]
-    pub inner: CapabilityDescriptor,
+    pub inner: CapabilityMap,
+}
+
+#[cfg(feature = "wasm")]
+impl Default for JsCapabilityDescriptor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(feature = "wasm")]
@@ -148,7 +158,7 @@ impl JsCapabilityDescriptor {
     #[wasm_bindgen(constructor)]
     pub fn new() -> JsCapabilityDescriptor {
         JsCapabilityDescriptor {
-            inner: CapabilityDescriptor::new(),
+            inner: CapabilityMap::new(),
         }
     }
 
@@ -174,7 +184,7 @@ impl JsCapabilityDescriptor {
 
     #[wasm_bindgen]
     pub fn from_js_object(obj: &JsValue) -> Result<JsCapabilityDescriptor, JsValue> {
-        let map: CapabilityDescriptor = serde_wasm_bindgen::from_value(obj.clone())
+        let map: CapabilityMap = serde_wasm_bindgen::from_value(obj.clone())
             .map_err(|e| JsValue::from_str(&format!("Failed to parse descriptor: {}", e)))?;
         Ok(JsCapabilityDescriptor { inner: map })
     }
@@ -192,11 +202,14 @@ mod tests {
 
     #[test]
     fn test_capability_descriptor_creation() {
-        let mut descriptor = CapabilityDescriptor::new();
+        let descriptor: CapabilityDescriptor = CapabilityDescriptor::builder().build().unwrap();
         assert!(descriptor.is_empty());
 
-        descriptor.insert("Read".to_string(), 0x1);
-        descriptor.insert("Write".to_string(), 0x2);
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .build()
+            .unwrap();
 
         assert_eq!(descriptor.len(), 2);
         assert_eq!(descriptor.get("Read"), Some(&0x1));
@@ -291,14 +304,15 @@ mod tests {
 
     #[test]
     fn test_descriptor_with_power_of_two_values() {
-        let mut descriptor = CapabilityDescriptor::new();
-
         // Add powers of 2 (typical permission pattern)
-        descriptor.insert("Permission0".to_string(), 0x1); // 2^0 = 1
-        descriptor.insert("Permission1".to_string(), 0x2); // 2^1 = 2
-        descriptor.insert("Permission2".to_string(), 0x4); // 2^2 = 4
-        descriptor.insert("Permission3".to_string(), 0x8); // 2^3 = 8
-        descriptor.insert("Permission4".to_string(), 0x10); // 2^4 = 16
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Permission0", 0x1) // 2^0 = 1
+            .capability("Permission1", 0x2) // 2^1 = 2
+            .capability("Permission2", 0x4) // 2^2 = 4
+            .capability("Permission3", 0x8) // 2^3 = 8
+            .capability("Permission4", 0x10) // 2^4 = 16
+            .build()
+            .unwrap();
 
         assert_eq!(descriptor.len(), 5);
 
@@ -312,9 +326,9 @@ mod tests {
 
     #[test]
     fn test_descriptor_with_overlapping_values() {
-        let mut descriptor = CapabilityDescriptor::new();
+        let mut descriptor = CapabilityMap::new();
 
-        // Add overlapping bit patterns (unusual but possible)
+        // Add overlapping bit patterns (possible in a raw map, rejected by the descriptor)
         descriptor.insert("Permission1".to_string(), 0x3); // Binary: 11
         descriptor.insert("Permission2".to_string(), 0x1); // Binary: 01 (overlaps)
         descriptor.insert("Permission3".to_string(), 0x2); // Binary: 10 (overlaps)
@@ -325,6 +339,8 @@ mod tests {
         assert_eq!(descriptor.get("Permission1"), Some(&0x3));
         assert_eq!(descriptor.get("Permission2"), Some(&0x1));
         assert_eq!(descriptor.get("Permission3"), Some(&0x2));
+
+        assert!(CapabilityDescriptor::try_from(descriptor).is_err());
     }
 
     #[test]
@@ -368,7 +384,7 @@ mod tests {
         let hex_value: CapilityHexValue = 0;
         let _: i32 = hex_value; // Should compile without error
 
-        let descriptor: CapabilityDescriptor = HashMap::new();
+        let descriptor: CapabilityMap = HashMap::new();
         let _: HashMap<String, i32> = descriptor; // Should compile without error
 
         let hex_set: CapabilityHexUnitSet = HashSet::new();
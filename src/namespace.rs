@@ -0,0 +1,247 @@
+//! # Namespace Module
+//!
+//! This module treats dotted capability names such as `files.read`, `files.write` and
+//! `billing.refund` as a hierarchy. Every prefix before a dot is a namespace (`files`,
+//! `billing`), and the wildcard `files.*` stands for every capability in the `files`
+//! namespace, at any depth.
+//!
+//! Wildcards are accepted wherever a name is:
+//!
+//! - [`RoleCapability::has_capability`] returns `true` for `files.*` only if the role holds
+//!   every capability of the namespace
+//! - [`RoleCapability::encode_names`], [`parse_value`](crate::parse::parse_value) and
+//!   [`Requirement::parse`](crate::requirement::Requirement::parse) expand `files.*` to
+//!   the whole namespace
+//! - [`RoleCapability::to_grouped_name_set`] writes fully held namespaces as wildcards
+//!
+//! A capability defined with a literal name always takes precedence over a wildcard
+//! of the same spelling.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("files.read", 0x1)
+//!     .capability("files.write", 0x2)
+//!     .capability("billing.refund", 0x4)
+//!     .build()
+//!     .unwrap();
+//!
+//! let value = RoleCapability::encode_names(&descriptor, ["files.*"]).unwrap();
+//! assert_eq!(value, 0x3);
+//!
+//! let role = RoleCapability::new(descriptor, 0x7);
+//! assert!(role.has_capability(&"files.*".to_string()));
+//!
+//! let mut names: Vec<String> = role.to_grouped_name_set().into_iter().collect();
+//! names.sort();
+//! assert_eq!(names, vec!["billing.*", "files.*"]);
+//! ```
+
+use std::collections::BTreeSet;
+
+use crate::models::{CapabilityDescriptor, CapabilityName, CapabilityNameSet};
+use crate::role_capability::RoleCapability;
+use crate::value::PermissionValue;
+
+/// Returns the namespace of a `namespace.*` wildcard, or `None` if the name is not one.
+pub(crate) fn split_wildcard(name: &str) -> Option<&str> {
+    name.strip_suffix(".*").filter(|prefix| !prefix.is_empty())
+}
+
+/// Returns `true` if the capability name lies in the namespace, at any depth.
+pub(crate) fn in_namespace(name: &str, namespace: &str) -> bool {
+    name.strip_prefix(namespace)
+        .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Returns the OR of the entries in the namespace, or `None` if there are none.
+pub(crate) fn namespace_value<'a, V, I>(entries: I, namespace: &str) -> Option<V>
+where
+    V: PermissionValue + 'a,
+    I: IntoIterator<Item = (&'a CapabilityName, &'a V)>,
+{
+    entries
+        .into_iter()
+        .filter(|(name, _)| in_namespace(name, namespace))
+        .map(|(_, value)| value.clone())
+        .reduce(|combined, value| combined.or(&value))
+}
+
+impl<V: PermissionValue> CapabilityDescriptor<V> {
+    /// Returns every namespace of the capability names, ordered by name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::models::CapabilityDescriptor;
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("files.read", 0x1)
+    ///     .capability("files.admin.delete", 0x2)
+    ///     .capability("Login", 0x4)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let namespaces: Vec<&str> = descriptor.namespaces().into_iter().collect();
+    /// assert_eq!(namespaces, vec!["files", "files.admin"]);
+    /// ```
+    pub fn namespaces(&self) -> BTreeSet<&str> {
+        self.names()
+            .flat_map(|name| {
+                name.match_indices('.')
+                    .map(move |(index, _)| &name[..index])
+            })
+            .filter(|namespace| !namespace.is_empty())
+            .collect()
+    }
+
+    /// Returns the combined value of every capability in the namespace, at any depth, or
+    /// `None` if the namespace is empty.
+    pub fn namespace(&self, namespace: &str) -> Option<V> {
+        namespace_value(self, namespace)
+    }
+}
+
+impl<V: PermissionValue> RoleCapability<V> {
+    /// Returns the names of the enabled capabilities, with every fully held namespace
+    /// written as a single `namespace.*` wildcard.
+    ///
+    /// Outer namespaces are grouped before the namespaces they contain, so holding all of
+    /// `files` yields `files.*` rather than `files.admin.*` and the rest. Capabilities of
+    /// a partly held namespace are listed individually, and level fields as
+    /// `Field=level`. Like [`to_name_set`](RoleCapability::to_name_set), only the bits
+    /// actually set count as held.
+    pub fn to_grouped_name_set(&self) -> CapabilityNameSet {
+        let descriptor = self.descriptor();
+        let mut namespaces: Vec<&str> = descriptor.namespaces().into_iter().collect();
+        namespaces.sort_by_key(|namespace| (namespace.matches('.').count(), *namespace));
+
+        let mut remaining = self.hex_value.clone();
+        let mut name_set = CapabilityNameSet::new();
+        for namespace in namespaces {
+            let Some(value) = descriptor.namespace(namespace) else {
+                continue;
+            };
+            if remaining.contains_all(&value) {
+                remaining = remaining.and_not(&value);
+                name_set.insert(format!("{}.*", namespace));
+            }
+        }
+        for (name, value) in descriptor {
+            if remaining.intersects(value) {
+                name_set.insert(name.clone());
+            }
+        }
+        name_set.extend(self.level_names().into_iter().map(|(_, name)| name));
+        name_set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::role_capability::CapabilityMatch;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("files.read", 0x1)
+            .capability("files.write", 0x2)
+            .capability("files.admin.delete", 0x4)
+            .capability("files.admin.restore", 0x8)
+            .capability("billing.refund", 0x10)
+            .capability("Login", 0x20)
+            .build()
+            .unwrap()
+    }
+
+    fn sorted(names: CapabilityNameSet) -> Vec<String> {
+        let mut names: Vec<String> = names.into_iter().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let descriptor = create_test_descriptor();
+
+        assert_eq!(
+            descriptor.namespaces().into_iter().collect::<Vec<_>>(),
+            vec!["billing", "files", "files.admin"]
+        );
+        assert_eq!(descriptor.namespace("files"), Some(0xF));
+        assert_eq!(descriptor.namespace("files.admin"), Some(0xC));
+        assert_eq!(descriptor.namespace("file"), None);
+        assert_eq!(descriptor.namespace("Login"), None);
+
+        assert_eq!(descriptor.resolve("files.admin.*"), Some(0xC));
+        assert_eq!(descriptor.resolve("files.read.*"), None);
+        assert_eq!(descriptor.resolve(".*"), None);
+    }
+
+    #[test]
+    fn test_wildcard_encoding() {
+        let descriptor = create_test_descriptor();
+
+        assert_eq!(
+            RoleCapability::encode_names(&descriptor, ["files.admin.*", "Login"]),
+            Ok(0x2C)
+        );
+        assert_eq!(
+            crate::parse::parse_value("files.* | billing.*", &descriptor),
+            Ok(0x1F)
+        );
+
+        let encoded = RoleCapability::encode_names_lenient(&descriptor, ["users.*"]);
+        assert_eq!(encoded.hex_value, 0x0);
+        assert_eq!(encoded.unknown, vec!["users.*"]);
+    }
+
+    #[test]
+    fn test_wildcard_requires_the_whole_namespace() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("files.read", 0x1)
+            .capability("files.write", 0x2)
+            .capability("files.share", 0x4)
+            .implies("files.write", ["files.read"])
+            .build()
+            .unwrap();
+
+        let partial = RoleCapability::new(descriptor.clone(), 0x6);
+        assert!(!partial.has_capability(&"files.*".to_string()));
+        assert!(partial.has_capability_with("files.*", CapabilityMatch::Implied));
+
+        let full = RoleCapability::new(descriptor, 0x7);
+        assert!(full.has_capability(&"files.*".to_string()));
+        assert!(full.has_capability_with("files.*", CapabilityMatch::AllBits));
+        assert!(!full.has_capability(&"billing.*".to_string()));
+    }
+
+    #[test]
+    fn test_grouped_name_set() {
+        let descriptor = create_test_descriptor();
+
+        let role = RoleCapability::new(descriptor.clone(), 0x3F);
+        assert_eq!(
+            sorted(role.to_grouped_name_set()),
+            vec!["Login", "billing.*", "files.*"]
+        );
+
+        let role = RoleCapability::new(descriptor.clone(), 0x0D);
+        assert_eq!(
+            sorted(role.to_grouped_name_set()),
+            vec!["files.admin.*", "files.read"]
+        );
+
+        let role = RoleCapability::new(descriptor, 0x5);
+        assert_eq!(
+            sorted(role.to_grouped_name_set()),
+            vec!["files.admin.delete", "files.read"]
+        );
+    }
+}
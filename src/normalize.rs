@@ -0,0 +1,221 @@
+//! # Normalize Module
+//!
+//! This module strips capabilities whose prerequisites are missing from a role. A
+//! prerequisite, declared with
+//! [`requires`](crate::models::CapabilityDescriptorBuilder::requires), is a capability
+//! another one is useless without, such as `ViewChannel` for `SendMessages`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("ViewChannel", 0x1)
+//!     .capability("SendMessages", 0x2)
+//!     .capability("AttachFiles", 0x4)
+//!     .requires("SendMessages", ["ViewChannel"])
+//!     .requires("AttachFiles", ["SendMessages"])
+//!     .build()
+//!     .unwrap();
+//!
+//! let role = RoleCapability::new(descriptor, 0x6);
+//! let normalized = role.normalize();
+//!
+//! assert_eq!(normalized.role.hex_value, 0x0);
+//! assert_eq!(
+//!     normalized.to_string(),
+//!     "removed SendMessages: requires ViewChannel\n\
+//!      removed AttachFiles: requires SendMessages\n"
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::models::{CapabilityName, CapilityHexValue};
+use crate::role_capability::RoleCapability;
+use crate::value::PermissionValue;
+
+/// A capability removed by [`RoleCapability::normalize`], with the prerequisites it was
+/// missing at the time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemovedCapability {
+    /// The removed capability.
+    pub name: CapabilityName,
+    /// Its prerequisites that were not held, in declaration order.
+    pub missing: Vec<CapabilityName>,
+}
+
+impl fmt::Display for RemovedCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "removed {}: requires {}",
+            self.name,
+            self.missing.join(", ")
+        )
+    }
+}
+
+/// The result of [`RoleCapability::normalize`]: the normalized role and the explanation of
+/// every removal.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Normalization<V = CapilityHexValue> {
+    /// The role without the capabilities whose prerequisites are missing.
+    pub role: RoleCapability<V>,
+    /// The removed capabilities, in the order they were removed.
+    pub removed: Vec<RemovedCapability>,
+}
+
+impl<V> Normalization<V> {
+    /// Returns `true` if no capability was removed.
+    pub fn is_unchanged(&self) -> bool {
+        self.removed.is_empty()
+    }
+}
+
+/// Renders one line per removed capability, such as
+/// `removed SendMessages: requires ViewChannel`.
+impl<V> fmt::Display for Normalization<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for removed in &self.removed {
+            writeln!(f, "{}", removed)?;
+        }
+        Ok(())
+    }
+}
+
+impl<V: PermissionValue> fmt::Debug for Normalization<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Normalization")
+            .field("role", &self.role)
+            .field("removed", &self.removed)
+            .finish()
+    }
+}
+
+impl<V: PermissionValue> RoleCapability<V> {
+    /// Returns the role without the capabilities whose prerequisites are missing.
+    ///
+    /// A prerequisite is met when its bit is set or a held bypass grants it. Removing a
+    /// capability can leave another one without its prerequisite, so removals repeat
+    /// until the value is stable. Within each pass, capabilities are checked in name order.
+    pub fn normalize(&self) -> Normalization<V> {
+        let descriptor = self.descriptor();
+        let mut value = self.hex_value.clone();
+        let mut removed = Vec::new();
+
+        loop {
+            let before = removed.len();
+            for (name, required) in descriptor.prerequisites() {
+                let Some(bit) = descriptor.get(name) else {
+                    continue;
+                };
+                if !value.intersects(bit) {
+                    continue;
+                }
+                let available = value.or(&descriptor.granted_by(&value));
+                let missing: Vec<CapabilityName> = required
+                    .iter()
+                    .filter(|prerequisite| {
+                        !descriptor
+                            .get(prerequisite)
+                            .is_some_and(|bit| available.intersects(bit))
+                    })
+                    .cloned()
+                    .collect();
+                if !missing.is_empty() {
+                    value = value.and_not(bit);
+                    removed.push(RemovedCapability {
+                        name: name.clone(),
+                        missing,
+                    });
+                }
+            }
+            if removed.len() == before {
+                break;
+            }
+        }
+
+        Normalization {
+            role: RoleCapability::new(descriptor.clone(), value),
+            removed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CapabilityDescriptor;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .capability("Send", 0x2)
+            .capability("Attach", 0x4)
+            .capability("Embed", 0x8)
+            .capability("Admin", 0x10)
+            .requires("Send", ["View"])
+            .requires("Attach", ["Send"])
+            .requires("Embed", ["View", "Send"])
+            .grants_all("Admin")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_complete_role_is_unchanged() {
+        let role = RoleCapability::new(create_test_descriptor(), 0xF);
+
+        let normalized = role.normalize();
+        assert!(normalized.is_unchanged());
+        assert_eq!(normalized.role, role);
+        assert_eq!(normalized.to_string(), "");
+    }
+
+    #[test]
+    fn test_missing_prerequisites_are_removed_transitively() {
+        let role = RoleCapability::new(create_test_descriptor(), 0xE);
+
+        let normalized = role.normalize();
+        assert_eq!(normalized.role.hex_value, 0x0);
+        assert_eq!(
+            normalized.removed,
+            vec![
+                RemovedCapability {
+                    name: "Embed".to_string(),
+                    missing: vec!["View".to_string()],
+                },
+                RemovedCapability {
+                    name: "Send".to_string(),
+                    missing: vec!["View".to_string()],
+                },
+                RemovedCapability {
+                    name: "Attach".to_string(),
+                    missing: vec!["Send".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unrelated_capabilities_are_kept() {
+        let role = RoleCapability::new(create_test_descriptor(), 0x5);
+
+        let normalized = role.normalize();
+        assert_eq!(normalized.role.hex_value, 0x1);
+        assert_eq!(normalized.to_string(), "removed Attach: requires Send\n");
+    }
+
+    #[test]
+    fn test_bypass_satisfies_prerequisites() {
+        let role = RoleCapability::new(create_test_descriptor(), 0x12);
+
+        assert!(role.normalize().is_unchanged());
+    }
+}
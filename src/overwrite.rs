@@ -0,0 +1,424 @@
+//! # Overwrite Module
+//!
+//! This module adjusts a member's permissions on a scoped resource, such as a channel,
+//! with allow and deny masks in the style of Discord channel permission overwrites.
+//!
+//! ## Precedence
+//!
+//! A [`PermissionOverwrite`] denies the bits of its deny mask, then allows the bits of its
+//! allow mask; every other bit is inherited unchanged. [`OverwriteLayers`] applies three
+//! layers in a fixed order, each one able to undo the previous:
+//!
+//! 1. The "everyone" overwrite
+//! 2. The overwrites of the member's roles, combined: all their denies are applied first,
+//!    then all their allows, so within this layer an allow wins over a deny
+//! 3. The member's own overwrite
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     overwrite::{OverwriteLayers, PermissionOverwrite},
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("View", 0x1)
+//!     .capability("Send", 0x2)
+//!     .capability("Attach", 0x4)
+//!     .build()
+//!     .unwrap();
+//!
+//! // Read-only channel, except for moderators, and one muted member
+//! let layers = OverwriteLayers::new()
+//!     .everyone(PermissionOverwrite::from_names(["View"], ["Send", "Attach"], &descriptor).unwrap())
+//!     .role("Moderator", PermissionOverwrite::from_names(["Send"], [] as [&str; 0], &descriptor).unwrap());
+//!
+//! let server_permissions = RoleCapability::new(descriptor.clone(), 0x6);
+//! assert_eq!(layers.apply_to(&server_permissions).hex_value, 0x3);
+//!
+//! let muted = layers.member(PermissionOverwrite::from_names([] as [&str; 0], ["Send"], &descriptor).unwrap());
+//! assert_eq!(muted.apply_to(&server_permissions).hex_value, 0x1);
+//! ```
+
+use std::fmt;
+
+use crate::checks::{validate, ValidationError};
+use crate::level::{union_levels, LevelField};
+use crate::models::{CapabilityDescriptor, CapilityHexValue};
+use crate::role_capability::{RoleCapability, UnknownCapabilityError};
+use crate::value::PermissionValue;
+
+/// Error returned when an overwrite is rejected by its descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverwriteError {
+    /// The allow mask is not a valid value for the descriptor.
+    InvalidAllow(ValidationError),
+    /// The deny mask is not a valid value for the descriptor.
+    InvalidDeny(ValidationError),
+    /// The same bits are both allowed and denied.
+    Conflict { bits: Vec<u32> },
+    /// A capability name is not defined in the descriptor.
+    UnknownCapability(UnknownCapabilityError),
+}
+
+impl fmt::Display for OverwriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverwriteError::InvalidAllow(error) => write!(f, "invalid allow mask: {}", error),
+            OverwriteError::InvalidDeny(error) => write!(f, "invalid deny mask: {}", error),
+            OverwriteError::Conflict { bits } => write!(
+                f,
+                "bit(s) {} are both allowed and denied",
+                bits.iter()
+                    .map(|bit| bit.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            OverwriteError::UnknownCapability(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for OverwriteError {}
+
+/// A pair of allow and deny masks, checked against a descriptor.
+///
+/// Bits in neither mask are inherited from the permissions the overwrite is applied to.
+/// An allowed level raises a level field to that level but never lowers it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionOverwrite<V = CapilityHexValue> {
+    allow: V,
+    deny: V,
+    fields: Vec<LevelField<V>>,
+}
+
+impl<V: PermissionValue> PermissionOverwrite<V> {
+    /// Creates an overwrite from allow and deny masks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OverwriteError`] if either mask is not valid for the descriptor or if
+    /// a bit is both allowed and denied.
+    pub fn new(
+        allow: V,
+        deny: V,
+        descriptor: &CapabilityDescriptor<V>,
+    ) -> Result<Self, OverwriteError> {
+        validate(&allow, descriptor).map_err(OverwriteError::InvalidAllow)?;
+        validate(&deny, descriptor).map_err(OverwriteError::InvalidDeny)?;
+        let conflict = allow.and(&deny);
+        if !conflict.is_zero() {
+            return Err(OverwriteError::Conflict {
+                bits: conflict.bit_positions(),
+            });
+        }
+        Ok(PermissionOverwrite {
+            allow,
+            deny,
+            fields: descriptor.level_fields().to_vec(),
+        })
+    }
+
+    /// Creates an overwrite from lists of allowed and denied capability names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OverwriteError`] if a name is unknown or is both allowed and denied.
+    pub fn from_names<A, D, S, T>(
+        allow: A,
+        deny: D,
+        descriptor: &CapabilityDescriptor<V>,
+    ) -> Result<Self, OverwriteError>
+    where
+        A: IntoIterator<Item = S>,
+        D: IntoIterator<Item = T>,
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let allow = RoleCapability::encode_names(descriptor, allow)
+            .map_err(OverwriteError::UnknownCapability)?;
+        let deny = RoleCapability::encode_names(descriptor, deny)
+            .map_err(OverwriteError::UnknownCapability)?;
+        Self::new(allow, deny, descriptor)
+    }
+
+    /// Returns the allow mask.
+    pub fn allow(&self) -> &V {
+        &self.allow
+    }
+
+    /// Returns the deny mask.
+    pub fn deny(&self) -> &V {
+        &self.deny
+    }
+
+    /// Applies the overwrite to a value: denied bits are cleared, then allowed bits are set.
+    pub fn apply(&self, value: &V) -> V {
+        self.grant(&value.and_not(&self.deny))
+    }
+
+    /// Sets the allowed bits in a value, keeping the higher level of each level field.
+    pub(crate) fn grant(&self, value: &V) -> V {
+        union_levels(&self.fields, value, &self.allow)
+    }
+}
+
+/// Identifies the layer an overwrite belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum OverwriteLayer {
+    /// The overwrite that applies to every member.
+    Everyone,
+    /// The overwrite for one of the member's roles, by name.
+    Role(String),
+    /// The overwrite for the member themselves.
+    Member,
+}
+
+impl fmt::Display for OverwriteLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverwriteLayer::Everyone => write!(f, "everyone overwrite"),
+            OverwriteLayer::Role(name) => write!(f, "role overwrite {:?}", name),
+            OverwriteLayer::Member => write!(f, "member overwrite"),
+        }
+    }
+}
+
+/// One precedence step: the deny mask of each overwrite, then the allow mask of each.
+pub(crate) struct OverwriteStep<'a, V> {
+    pub(crate) denies: Vec<(OverwriteLayer, &'a PermissionOverwrite<V>)>,
+    pub(crate) allows: Vec<(OverwriteLayer, &'a PermissionOverwrite<V>)>,
+}
+
+/// The overwrites that apply to a member on one resource, applied in precedence order.
+///
+/// See the [module documentation](self) for the precedence rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverwriteLayers<V = CapilityHexValue> {
+    everyone: Option<PermissionOverwrite<V>>,
+    roles: Vec<(String, PermissionOverwrite<V>)>,
+    member: Option<PermissionOverwrite<V>>,
+}
+
+impl<V: PermissionValue> Default for OverwriteLayers<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: PermissionValue> OverwriteLayers<V> {
+    /// Creates an empty set of layers, which leaves permissions unchanged.
+    pub fn new() -> Self {
+        OverwriteLayers {
+            everyone: None,
+            roles: Vec::new(),
+            member: None,
+        }
+    }
+
+    /// Sets the overwrite that applies to every member.
+    pub fn everyone(mut self, overwrite: PermissionOverwrite<V>) -> Self {
+        self.everyone = Some(overwrite);
+        self
+    }
+
+    /// Adds the overwrite of one of the member's roles.
+    pub fn role(mut self, name: impl Into<String>, overwrite: PermissionOverwrite<V>) -> Self {
+        self.roles.push((name.into(), overwrite));
+        self
+    }
+
+    /// Sets the overwrite for the member themselves.
+    pub fn member(mut self, overwrite: PermissionOverwrite<V>) -> Self {
+        self.member = Some(overwrite);
+        self
+    }
+
+    /// Applies every layer to a value.
+    pub fn apply(&self, value: &V) -> V {
+        let mut value = value.clone();
+        for step in self.steps() {
+            for (_, overwrite) in &step.denies {
+                value = value.and_not(overwrite.deny());
+            }
+            for (_, overwrite) in &step.allows {
+                value = overwrite.grant(&value);
+            }
+        }
+        value
+    }
+
+    /// Applies every layer to a role's value and returns the resulting role.
+    pub fn apply_to(&self, role: &RoleCapability<V>) -> RoleCapability<V> {
+        RoleCapability::new(role.descriptor().clone(), self.apply(&role.hex_value))
+    }
+
+    /// Returns the precedence steps, in the order they are applied.
+    pub(crate) fn steps(&self) -> Vec<OverwriteStep<'_, V>> {
+        let roles = OverwriteStep {
+            denies: self
+                .roles
+                .iter()
+                .map(|(name, overwrite)| (OverwriteLayer::Role(name.clone()), overwrite))
+                .collect(),
+            allows: self
+                .roles
+                .iter()
+                .map(|(name, overwrite)| (OverwriteLayer::Role(name.clone()), overwrite))
+                .collect(),
+        };
+
+        vec![
+            single_step(OverwriteLayer::Everyone, &self.everyone),
+            roles,
+            single_step(OverwriteLayer::Member, &self.member),
+        ]
+    }
+}
+
+fn single_step<V>(
+    layer: OverwriteLayer,
+    overwrite: &Option<PermissionOverwrite<V>>,
+) -> OverwriteStep<'_, V> {
+    let mut step = OverwriteStep {
+        denies: Vec::new(),
+        allows: Vec::new(),
+    };
+    if let Some(overwrite) = overwrite {
+        step.denies.push((layer.clone(), overwrite));
+        step.allows.push((layer, overwrite));
+    }
+    step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::ValidationRule;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .capability("Send", 0x2)
+            .capability("Attach", 0x4)
+            .capability("Manage", 0x8)
+            .build()
+            .unwrap()
+    }
+
+    fn overwrite(allow: i32, deny: i32) -> PermissionOverwrite {
+        PermissionOverwrite::new(allow, deny, &create_test_descriptor()).unwrap()
+    }
+
+    #[test]
+    fn test_overwrite_apply() {
+        let ow = overwrite(0x4, 0x2);
+        assert_eq!(ow.apply(&0x3), 0x5);
+        // Bits in neither mask are inherited
+        assert_eq!(ow.apply(&0x8), 0xC);
+        assert_eq!(ow.allow(), &0x4);
+        assert_eq!(ow.deny(), &0x2);
+    }
+
+    #[test]
+    fn test_overwrite_is_checked() {
+        let descriptor = create_test_descriptor();
+
+        let error = PermissionOverwrite::new(0x10, 0x0, &descriptor).unwrap_err();
+        match error {
+            OverwriteError::InvalidAllow(validation) => {
+                assert_eq!(validation.rule(), ValidationRule::UnknownBits)
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(matches!(
+            PermissionOverwrite::new(0x0, 0x20, &descriptor),
+            Err(OverwriteError::InvalidDeny(_))
+        ));
+
+        let error = PermissionOverwrite::new(0x3, 0x6, &descriptor).unwrap_err();
+        assert_eq!(error, OverwriteError::Conflict { bits: vec![1] });
+        assert_eq!(error.to_string(), "bit(s) 1 are both allowed and denied");
+    }
+
+    #[test]
+    fn test_overwrite_from_names() {
+        let descriptor = create_test_descriptor();
+
+        let ow = PermissionOverwrite::from_names(["View"], ["Send"], &descriptor).unwrap();
+        assert_eq!(ow, overwrite(0x1, 0x2));
+
+        let error = PermissionOverwrite::from_names(["Fly"], ["Send"], &descriptor).unwrap_err();
+        assert!(matches!(error, OverwriteError::UnknownCapability(_)));
+    }
+
+    #[test]
+    fn test_empty_layers_change_nothing() {
+        let layers: OverwriteLayers = OverwriteLayers::new();
+        assert_eq!(layers.apply(&0xB), 0xB);
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        // Everyone: deny Send. Role: allow Send. Member: deny Send again.
+        let layers = OverwriteLayers::new().everyone(overwrite(0x0, 0x2));
+        assert_eq!(layers.apply(&0x3), 0x1);
+
+        let layers = layers.role("Speaker", overwrite(0x2, 0x0));
+        assert_eq!(layers.apply(&0x3), 0x3);
+
+        let layers = layers.member(overwrite(0x0, 0x2));
+        assert_eq!(layers.apply(&0x3), 0x1);
+    }
+
+    #[test]
+    fn test_role_allow_beats_role_deny() {
+        let layers = OverwriteLayers::new()
+            .role("Muted", overwrite(0x0, 0x2))
+            .role("Speaker", overwrite(0x2, 0x0));
+        assert_eq!(layers.apply(&0x0), 0x2);
+
+        // Order of role overwrites does not matter
+        let reversed = OverwriteLayers::new()
+            .role("Speaker", overwrite(0x2, 0x0))
+            .role("Muted", overwrite(0x0, 0x2));
+        assert_eq!(reversed.apply(&0x0), 0x2);
+    }
+
+    #[test]
+    fn test_allowed_levels_never_lower_a_level() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .level_field("FileAccess", 0x6, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+        let read =
+            PermissionOverwrite::from_names(["FileAccess=read"], [] as [&str; 0], &descriptor)
+                .unwrap();
+
+        // write (0b10) allowed read (0b01) stays write rather than becoming admin (0b11)
+        assert_eq!(read.apply(&0x5), 0x5);
+        assert_eq!(read.apply(&0x1), 0x3);
+
+        let layers = OverwriteLayers::new().role("Reader", read).role(
+            "Writer",
+            PermissionOverwrite::new(0x4, 0x0, &descriptor).unwrap(),
+        );
+        assert_eq!(layers.apply(&0x1), 0x5);
+    }
+
+    #[test]
+    fn test_apply_to_role() {
+        let descriptor = create_test_descriptor();
+        let role = RoleCapability::new(descriptor, 0xF);
+        let layers = OverwriteLayers::new().member(overwrite(0x0, 0x8));
+
+        let adjusted = layers.apply_to(&role);
+        assert_eq!(adjusted.hex_value, 0x7);
+        assert!(!adjusted.has_capability(&"Manage".to_string()));
+    }
+}
@@ -0,0 +1,483 @@
+//! # Parse Module
+//!
+//! This module turns the textual forms of a permission value found in configuration files
+//! and admin interfaces into a value, and checks it against a descriptor.
+//!
+//! ## Accepted Forms
+//!
+//! - Hexadecimal: `"0x1F"` (prefix `0x` or `0X`)
+//! - Decimal: `"31"`
+//! - Binary: `"0b11111"` (prefix `0b` or `0B`)
+//! - Octal: `"0o37"` (prefix `0o` or `0O`)
+//! - Name expression: `"Read|Write|Admin"`
+//!
+//! Numbers may use `_` as a digit separator (`"0b1111_0000"`) and surrounding whitespace is
+//! ignored, as is whitespace around the names of an expression.
+//!
+//! ## Error Positions
+//!
+//! Every [`ParseError`] carries the byte range of the offending input, so a caller can point
+//! at the bad digit or the unknown name:
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     parse::{parse_value, ParseErrorKind},
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Write", 0x2)
+//!     .build()
+//!     .unwrap();
+//!
+//! let input = "Read|Wrte";
+//! let error = parse_value(input, &descriptor).unwrap_err();
+//! assert_eq!(error.position(), 5);
+//! assert_eq!(&input[error.span()], "Wrte");
+//! assert!(matches!(error.kind(), ParseErrorKind::UnknownCapability { .. }));
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::checks::{validate, ValidationError};
+use crate::descriptor::DescriptorLike;
+use crate::level::union_levels;
+#[cfg(feature = "wasm")]
+use crate::models::CapilityHexValue;
+use crate::value::PermissionValue;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The reason a string could not be parsed into a permission value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input is empty or only whitespace.
+    Empty,
+    /// A radix prefix such as `0x` is not followed by any digit.
+    MissingDigits,
+    /// A character is not a valid digit for the radix.
+    InvalidDigit { digit: char, radix: u32 },
+    /// The number does not fit in the value type.
+    Overflow,
+    /// A name expression contains an empty name, as in `"Read||Write"`.
+    EmptyName,
+    /// A name is not defined in the descriptor.
+    UnknownCapability { name: String },
+    /// The value was parsed but is not valid for the descriptor.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Empty => write!(f, "empty permission value"),
+            ParseErrorKind::MissingDigits => write!(f, "missing digits after radix prefix"),
+            ParseErrorKind::InvalidDigit { digit, radix } => {
+                write!(f, "invalid digit {:?} for base {}", digit, radix)
+            }
+            ParseErrorKind::Overflow => write!(f, "number is too large for the value type"),
+            ParseErrorKind::EmptyName => write!(f, "empty capability name"),
+            ParseErrorKind::UnknownCapability { name } => {
+                write!(f, "unknown capability {:?}", name)
+            }
+            ParseErrorKind::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Error returned when a permission value cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: Range<usize>,
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, span: Range<usize>) -> Self {
+        ParseError { kind, span }
+    }
+
+    /// Returns the reason for the failure.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte offset in the input where the problem starts.
+    pub fn position(&self) -> usize {
+        self.span.start
+    }
+
+    /// Returns the byte range of the offending input.
+    ///
+    /// For [`ParseErrorKind::Invalid`] this covers the whole trimmed input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.span.start)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a permission value in any accepted form and checks it against a descriptor.
+///
+/// Numeric forms are validated with [`validate`], so values with unknown bits are rejected.
+/// Name expressions are resolved through the descriptor.
+///
+/// # Arguments
+///
+/// * `input` - The text to parse, such as `"0x1F"`, `"31"`, `"0b11111"` or `"Read|Write"`
+/// * `descriptor` - The capability descriptor to resolve names and validate against
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] with the position of the bad input.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{models::CapabilityDescriptor, parse::parse_value};
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("Write", 0x2)
+///     .capability("Execute", 0x4)
+///     .capability("Delete", 0x8)
+///     .capability("Admin", 0x10)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(parse_value("0x1F", &descriptor), Ok(0x1F));
+/// assert_eq!(parse_value("31", &descriptor), Ok(0x1F));
+/// assert_eq!(parse_value("0b11111", &descriptor), Ok(0x1F));
+/// assert_eq!(parse_value("Read|Write|Admin", &descriptor), Ok(0x13));
+///
+/// assert!(parse_value("0x20", &descriptor).is_err()); // Bit 5 is not defined
+/// ```
+pub fn parse_value<D: DescriptorLike>(input: &str, descriptor: &D) -> Result<D::Value, ParseError> {
+    let (start, text) = trim_with_offset(input, 0);
+    if text.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::Empty, start..start));
+    }
+
+    let value = if text.starts_with(|c: char| c.is_ascii_digit()) {
+        parse_number_at(text, start)?
+    } else {
+        parse_names_at(text, start, descriptor)?
+    };
+
+    validate(&value, descriptor).map_err(|error| {
+        ParseError::new(ParseErrorKind::Invalid(error), start..start + text.len())
+    })?;
+    Ok(value)
+}
+
+/// Parses a numeric permission value without checking it against a descriptor.
+///
+/// Accepts the hexadecimal, decimal, binary and octal forms, but not name expressions.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::parse::parse_number;
+///
+/// assert_eq!(parse_number::<u64>("0xFFFF_FFFF_FFFF"), Ok(0xFFFF_FFFF_FFFF));
+///
+/// let error = parse_number::<i32>("0x1G").unwrap_err();
+/// assert_eq!(error.position(), 3);
+/// ```
+pub fn parse_number<V: PermissionValue>(input: &str) -> Result<V, ParseError> {
+    let (start, text) = trim_with_offset(input, 0);
+    if text.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::Empty, start..start));
+    }
+    parse_number_at(text, start)
+}
+
+/// Trims whitespace from `text`, returning the offset of the trimmed text relative to
+/// `offset`.
+fn trim_with_offset(text: &str, offset: usize) -> (usize, &str) {
+    let trimmed_start = text.trim_start();
+    let start = offset + (text.len() - trimmed_start.len());
+    (start, trimmed_start.trim_end())
+}
+
+/// Parses a trimmed, non-empty number that starts at byte `start` of the original input.
+fn parse_number_at<V: PermissionValue>(text: &str, start: usize) -> Result<V, ParseError> {
+    let (radix, prefix_len) = match text.get(..2) {
+        Some("0x") | Some("0X") => (16, 2),
+        Some("0b") | Some("0B") => (2, 2),
+        Some("0o") | Some("0O") => (8, 2),
+        _ => (10, 0),
+    };
+    let body = &text[prefix_len..];
+    let body_start = start + prefix_len;
+
+    let mut digits = String::with_capacity(body.len());
+    for (index, c) in body.char_indices() {
+        if c == '_' {
+            continue;
+        }
+        if !c.is_digit(radix) {
+            let position = body_start + index;
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidDigit { digit: c, radix },
+                position..position + c.len_utf8(),
+            ));
+        }
+        digits.push(c);
+    }
+    if digits.is_empty() {
+        return Err(ParseError::new(
+            ParseErrorKind::MissingDigits,
+            start..start + text.len(),
+        ));
+    }
+
+    V::from_str_radix(&digits, radix)
+        .ok_or_else(|| ParseError::new(ParseErrorKind::Overflow, start..start + text.len()))
+}
+
+/// Parses a trimmed `Name|Name|...` expression that starts at byte `start` of the
+/// original input. A name may also be a `Field=level` of a level field.
+fn parse_names_at<D: DescriptorLike>(
+    text: &str,
+    start: usize,
+    descriptor: &D,
+) -> Result<D::Value, ParseError> {
+    let mut value = D::Value::zero();
+    let mut offset = start;
+    for term in text.split('|') {
+        let (name_start, name) = trim_with_offset(term, offset);
+        if name.is_empty() {
+            return Err(ParseError::new(
+                ParseErrorKind::EmptyName,
+                name_start..name_start,
+            ));
+        }
+        match descriptor.resolve(name) {
+            Some(capability) => {
+                value = union_levels(descriptor.level_fields(), &value, &capability)
+            }
+            None => {
+                return Err(ParseError::new(
+                    ParseErrorKind::UnknownCapability {
+                        name: name.to_string(),
+                    },
+                    name_start..name_start + name.len(),
+                ))
+            }
+        }
+        offset += term.len() + 1;
+    }
+    Ok(value)
+}
+
+/// WASM wrapper for [`parse_value`].
+///
+/// The error message includes the position of the bad input.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn js_parse_value(
+    input: &str,
+    descriptor: &crate::models::JsCapabilityDescriptor,
+) -> Result<CapilityHexValue, JsValue> {
+    parse_value(input, &descriptor.inner).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::ValidationRule;
+    use crate::models::{CapabilityDescriptor, CapabilityMap};
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Delete", 0x8)
+            .capability("Admin", 0x10)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_all_forms() {
+        let descriptor = create_test_descriptor();
+
+        assert_eq!(parse_value("0x1F", &descriptor), Ok(0x1F));
+        assert_eq!(parse_value("0X1f", &descriptor), Ok(0x1F));
+        assert_eq!(parse_value("31", &descriptor), Ok(0x1F));
+        assert_eq!(parse_value("0b11111", &descriptor), Ok(0x1F));
+        assert_eq!(parse_value("0o37", &descriptor), Ok(0x1F));
+        assert_eq!(
+            parse_value("Read|Write|Execute|Delete|Admin", &descriptor),
+            Ok(0x1F)
+        );
+        assert_eq!(parse_value("0", &descriptor), Ok(0x0));
+    }
+
+    #[test]
+    fn test_parse_whitespace_and_separators() {
+        let descriptor = create_test_descriptor();
+
+        assert_eq!(parse_value("  0x1F\n", &descriptor), Ok(0x1F));
+        assert_eq!(parse_value("0b1_1111", &descriptor), Ok(0x1F));
+        assert_eq!(parse_value(" Read | Admin ", &descriptor), Ok(0x11));
+    }
+
+    #[test]
+    fn test_parse_empty_input() {
+        let descriptor = create_test_descriptor();
+
+        let error = parse_value("   ", &descriptor).unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::Empty);
+        assert_eq!(error.position(), 3);
+    }
+
+    #[test]
+    fn test_parse_invalid_digit_position() {
+        let descriptor = create_test_descriptor();
+
+        let error = parse_value("0x1G", &descriptor).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::InvalidDigit {
+                digit: 'G',
+                radix: 16
+            }
+        );
+        assert_eq!(error.span(), 3..4);
+
+        let error = parse_value("  0b102", &descriptor).unwrap_err();
+        assert_eq!(error.position(), 6);
+
+        let error = parse_value("12a", &descriptor).unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert_eq!(
+            error.to_string(),
+            "invalid digit 'a' for base 10 at position 2"
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_digits() {
+        let descriptor = create_test_descriptor();
+
+        let error = parse_value("0x", &descriptor).unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::MissingDigits);
+        assert_eq!(error.span(), 0..2);
+
+        let error = parse_value("0b__", &descriptor).unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::MissingDigits);
+    }
+
+    #[test]
+    fn test_parse_rejects_signs() {
+        let error = parse_number::<i32>("0x-1").unwrap_err();
+        assert_eq!(error.position(), 2);
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        let error = parse_number::<u32>("0x1_0000_0000").unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::Overflow);
+        assert_eq!(error.span(), 0..13);
+
+        assert_eq!(parse_number::<u64>("0x1_0000_0000"), Ok(0x1_0000_0000));
+    }
+
+    #[test]
+    fn test_parse_unknown_name_position() {
+        let descriptor = create_test_descriptor();
+
+        let input = "Read| Wrte |Admin";
+        let error = parse_value(input, &descriptor).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::UnknownCapability {
+                name: "Wrte".to_string()
+            }
+        );
+        assert_eq!(error.span(), 6..10);
+        assert_eq!(&input[error.span()], "Wrte");
+    }
+
+    #[test]
+    fn test_parse_empty_name() {
+        let descriptor = create_test_descriptor();
+
+        let error = parse_value("Read||Write", &descriptor).unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::EmptyName);
+        assert_eq!(error.position(), 5);
+
+        let error = parse_value("Read|", &descriptor).unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::EmptyName);
+        assert_eq!(error.position(), 5);
+    }
+
+    #[test]
+    fn test_parse_composites() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Admin", 0x4)
+            .composite("ReadWrite", 0x3)
+            .build()
+            .unwrap();
+
+        assert_eq!(parse_value("ReadWrite", &descriptor), Ok(0x3));
+        assert_eq!(parse_value("ReadWrite | Admin", &descriptor), Ok(0x7));
+    }
+
+    #[test]
+    fn test_parse_checks_descriptor() {
+        let descriptor = create_test_descriptor();
+
+        let error = parse_value(" 0x20", &descriptor).unwrap_err();
+        match error.kind() {
+            ParseErrorKind::Invalid(validation) => {
+                assert_eq!(validation.rule(), ValidationRule::UnknownBits);
+                assert_eq!(validation.bits(), &[5]);
+            }
+            other => panic!("unexpected error kind: {:?}", other),
+        }
+        assert_eq!(error.span(), 1..5);
+    }
+
+    #[test]
+    fn test_parse_against_corrupted_map() {
+        let mut map = CapabilityMap::new();
+        map.insert("Read".to_string(), 0x1);
+        map.insert("Broken".to_string(), -0x4);
+
+        let error = parse_value("Read", &map).unwrap_err();
+        assert!(matches!(error.kind(), ParseErrorKind::Invalid(_)));
+    }
+
+    #[test]
+    fn test_parse_wide_values() {
+        let descriptor = CapabilityDescriptor::<u128>::builder()
+            .capability("Low", 0x1)
+            .capability("High", 1 << 100)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            parse_value("Low|High", &descriptor),
+            Ok((1u128 << 100) | 0x1)
+        );
+        assert_eq!(
+            parse_value("0x10000000000000000000000001", &descriptor),
+            Ok((1u128 << 100) | 0x1)
+        );
+    }
+}
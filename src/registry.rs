@@ -0,0 +1,590 @@
+//! # Registry Module
+//!
+//! This module holds several permission domains side by side, such as `server`, `channel`
+//! and `billing`, each with its own [`CapabilityDescriptor`]. A [`DescriptorRegistry`]
+//! resolves qualified names like `channel:SendMessages`, and a [`DomainPermissions`] value
+//! carries one bitmask per domain.
+//!
+//! The part after the colon is any name the domain's descriptor resolves: a capability, a
+//! composite, a `namespace.*` wildcard or a `Field=level`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     registry::DescriptorRegistry,
+//! };
+//!
+//! let server = CapabilityDescriptor::builder()
+//!     .capability("Kick", 0x1)
+//!     .capability("Ban", 0x2)
+//!     .build()
+//!     .unwrap();
+//! let channel = CapabilityDescriptor::builder()
+//!     .capability("ViewChannel", 0x1)
+//!     .capability("SendMessages", 0x2)
+//!     .build()
+//!     .unwrap();
+//!
+//! let registry = DescriptorRegistry::builder()
+//!     .domain("server", server)
+//!     .domain("channel", channel)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(registry.resolve("channel:SendMessages").unwrap(), ("channel", 0x2));
+//!
+//! let permissions = registry
+//!     .encode(["server:Kick", "channel:ViewChannel", "channel:SendMessages"])
+//!     .unwrap();
+//! assert_eq!(permissions.get("channel"), Some(&0x3));
+//! assert!(registry.has_capability(&permissions, "server:Kick"));
+//! assert!(!registry.has_capability(&permissions, "server:Ban"));
+//! assert_eq!(
+//!     registry.decode(&permissions),
+//!     vec!["channel:ViewChannel", "channel:SendMessages", "server:Kick"]
+//! );
+//! ```
+
+use std::collections::{btree_map, BTreeMap};
+use std::fmt;
+
+use crate::checks::{validate, ValidationError};
+use crate::level::union_levels;
+use crate::models::{CapabilityDescriptor, CapabilityName, CapilityHexValue};
+use crate::role_capability::{CapabilityMatch, CapabilityOrder, RoleCapability};
+use crate::value::PermissionValue;
+
+/// Separates the domain from the capability in a qualified name.
+pub const DOMAIN_SEPARATOR: char = ':';
+
+/// Error returned by a [`DescriptorRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The same domain was added twice.
+    DuplicateDomain { domain: String },
+    /// The domain name is empty or contains the `:` separator.
+    InvalidDomainName { domain: String },
+    /// A name is not qualified with a domain.
+    MissingDomain { name: String },
+    /// The registry has no such domain.
+    UnknownDomain { domain: String },
+    /// The domain's descriptor does not resolve the name.
+    UnknownCapability { domain: String, name: String },
+    /// The value of a domain is rejected by its descriptor.
+    InvalidValue {
+        domain: String,
+        error: ValidationError,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::DuplicateDomain { domain } => {
+                write!(f, "domain {:?} is registered more than once", domain)
+            }
+            RegistryError::InvalidDomainName { domain } => write!(
+                f,
+                "domain name {:?} must be non-empty and must not contain {:?}",
+                domain, DOMAIN_SEPARATOR
+            ),
+            RegistryError::MissingDomain { name } => {
+                write!(f, "name {:?} is not qualified with a domain", name)
+            }
+            RegistryError::UnknownDomain { domain } => write!(f, "unknown domain {:?}", domain),
+            RegistryError::UnknownCapability { domain, name } => {
+                write!(f, "unknown capability {:?} in domain {:?}", name, domain)
+            }
+            RegistryError::InvalidValue { domain, error } => {
+                write!(f, "invalid value for domain {:?}: {}", domain, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// A permission value with one bitmask per domain of a [`DescriptorRegistry`].
+///
+/// A domain without a bitmask holds nothing. With the `serde` feature the value
+/// serializes as a map from domain to bitmask, such as `{"channel":3,"server":1}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct DomainPermissions<V = CapilityHexValue> {
+    values: BTreeMap<String, V>,
+}
+
+impl<V: PermissionValue> DomainPermissions<V> {
+    /// Creates a value holding nothing in any domain.
+    pub fn new() -> Self {
+        DomainPermissions {
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the bitmask of a domain, if one is set.
+    pub fn get(&self, domain: &str) -> Option<&V> {
+        self.values.get(domain)
+    }
+
+    /// Sets the bitmask of a domain, replacing any previous one. A zero bitmask removes
+    /// the domain.
+    pub fn set(&mut self, domain: impl Into<String>, value: V) {
+        let domain = domain.into();
+        if value.is_zero() {
+            self.values.remove(&domain);
+        } else {
+            self.values.insert(domain, value);
+        }
+    }
+
+    /// Iterates over the domains with a bitmask, ordered by domain name.
+    pub fn iter(&self) -> btree_map::Iter<'_, String, V> {
+        self.values.iter()
+    }
+
+    /// Returns `true` if no domain holds anything.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a DomainPermissions<V> {
+    type Item = (&'a String, &'a V);
+    type IntoIter = btree_map::Iter<'a, String, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+/// Named descriptors for several permission domains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorRegistry<V = CapilityHexValue> {
+    domains: BTreeMap<String, CapabilityDescriptor<V>>,
+}
+
+impl<V: PermissionValue> DescriptorRegistry<V> {
+    /// Creates a builder for a registry.
+    pub fn builder() -> DescriptorRegistryBuilder<V> {
+        DescriptorRegistryBuilder::new()
+    }
+
+    /// Returns the descriptor of a domain, if it exists.
+    pub fn get(&self, domain: &str) -> Option<&CapabilityDescriptor<V>> {
+        self.domains.get(domain)
+    }
+
+    /// Iterates over the domains and their descriptors, ordered by domain name.
+    pub fn domains(&self) -> btree_map::Iter<'_, String, CapabilityDescriptor<V>> {
+        self.domains.iter()
+    }
+
+    /// Returns the number of domains.
+    pub fn len(&self) -> usize {
+        self.domains.len()
+    }
+
+    /// Returns `true` if the registry has no domains.
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    /// Resolves a qualified name such as `channel:SendMessages` into its domain and value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RegistryError`] if the name has no domain, the domain does not exist,
+    /// or the domain's descriptor does not resolve the name.
+    pub fn resolve<'a>(&'a self, qualified: &str) -> Result<(&'a str, V), RegistryError> {
+        let (domain, name) =
+            qualified
+                .split_once(DOMAIN_SEPARATOR)
+                .ok_or_else(|| RegistryError::MissingDomain {
+                    name: qualified.to_string(),
+                })?;
+        let (domain, descriptor) =
+            self.domains
+                .get_key_value(domain)
+                .ok_or_else(|| RegistryError::UnknownDomain {
+                    domain: domain.to_string(),
+                })?;
+        let value = descriptor
+            .resolve(name)
+            .ok_or_else(|| RegistryError::UnknownCapability {
+                domain: domain.clone(),
+                name: name.to_string(),
+            })?;
+        Ok((domain, value))
+    }
+
+    /// Encodes qualified names into a value with one bitmask per domain.
+    ///
+    /// Names of the same domain are combined with OR, except that a level field named
+    /// more than once holds the highest of its levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`RegistryError`] of the first name that cannot be resolved.
+    pub fn encode<I, S>(&self, names: I) -> Result<DomainPermissions<V>, RegistryError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut values: BTreeMap<String, V> = BTreeMap::new();
+        for name in names {
+            let (domain, value) = self.resolve(name.as_ref())?;
+            let fields = self.domains[domain].level_fields();
+            let combined = values.entry(domain.to_string()).or_insert_with(V::zero);
+            *combined = union_levels(fields, combined, &value);
+        }
+        let mut permissions = DomainPermissions::new();
+        for (domain, value) in values {
+            permissions.set(domain, value);
+        }
+        Ok(permissions)
+    }
+
+    /// Returns the qualified names held by a value, ordered by domain name and then by
+    /// bit position. Domains the registry does not know are skipped.
+    pub fn decode(&self, permissions: &DomainPermissions<V>) -> Vec<CapabilityName> {
+        let mut names = Vec::new();
+        for domain in self.domains.keys() {
+            let Some(role) = self.role(permissions, domain) else {
+                continue;
+            };
+            names.extend(
+                role.to_name_list(CapabilityOrder::BitPosition)
+                    .into_iter()
+                    .map(|name| format!("{}{}{}", domain, DOMAIN_SEPARATOR, name)),
+            );
+        }
+        names
+    }
+
+    /// Returns the role a value holds in one domain, or `None` if the domain does not
+    /// exist.
+    pub fn role(
+        &self,
+        permissions: &DomainPermissions<V>,
+        domain: &str,
+    ) -> Option<RoleCapability<V>> {
+        let descriptor = self.domains.get(domain)?;
+        let value = permissions.get(domain).cloned().unwrap_or_else(V::zero);
+        Some(RoleCapability::new(descriptor.clone(), value))
+    }
+
+    /// Returns `true` if the value holds a qualified capability, as decided by
+    /// [`RoleCapability::has_capability`] in its domain. Unknown domains and names are
+    /// never held.
+    pub fn has_capability(&self, permissions: &DomainPermissions<V>, qualified: &str) -> bool {
+        let Some((domain, name)) = qualified.split_once(DOMAIN_SEPARATOR) else {
+            return false;
+        };
+        self.role(permissions, domain)
+            .is_some_and(|role| role.has_capability_with(name, CapabilityMatch::Direct))
+    }
+
+    /// Checks every bitmask of a value against the descriptor of its domain.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RegistryError`] for the first domain, by name, that the registry does
+    /// not know or whose descriptor rejects the bitmask.
+    pub fn validate(&self, permissions: &DomainPermissions<V>) -> Result<(), RegistryError> {
+        for (domain, value) in permissions {
+            let descriptor =
+                self.domains
+                    .get(domain)
+                    .ok_or_else(|| RegistryError::UnknownDomain {
+                        domain: domain.clone(),
+                    })?;
+            validate(value, descriptor).map_err(|error| RegistryError::InvalidValue {
+                domain: domain.clone(),
+                error,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`DescriptorRegistry`].
+///
+/// The first rejected domain is reported by [`build`](DescriptorRegistryBuilder::build)
+/// and later domains are ignored.
+#[derive(Debug, Clone)]
+pub struct DescriptorRegistryBuilder<V = CapilityHexValue> {
+    domains: BTreeMap<String, CapabilityDescriptor<V>>,
+    error: Option<RegistryError>,
+}
+
+impl<V: PermissionValue> Default for DescriptorRegistryBuilder<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: PermissionValue> DescriptorRegistryBuilder<V> {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        DescriptorRegistryBuilder {
+            domains: BTreeMap::new(),
+            error: None,
+        }
+    }
+
+    /// Adds a domain with its descriptor.
+    ///
+    /// The domain is rejected if its name is empty, contains `:` or is already registered.
+    pub fn domain(mut self, name: impl Into<String>, descriptor: CapabilityDescriptor<V>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let domain = name.into();
+        if domain.is_empty() || domain.contains(DOMAIN_SEPARATOR) {
+            self.error = Some(RegistryError::InvalidDomainName { domain });
+        } else {
+            match self.domains.entry(domain) {
+                btree_map::Entry::Occupied(entry) => {
+                    self.error = Some(RegistryError::DuplicateDomain {
+                        domain: entry.key().clone(),
+                    });
+                }
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(descriptor);
+                }
+            }
+        }
+        self
+    }
+
+    /// Builds the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`RegistryError`] recorded while adding domains.
+    pub fn build(self) -> Result<DescriptorRegistry<V>, RegistryError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(DescriptorRegistry {
+                domains: self.domains,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::ValidationRule;
+
+    fn create_test_registry() -> DescriptorRegistry {
+        let server = CapabilityDescriptor::builder()
+            .capability("Kick", 0x1)
+            .capability("Ban", 0x2)
+            .build()
+            .unwrap();
+        let channel = CapabilityDescriptor::builder()
+            .capability("View", 0x1)
+            .capability("Send", 0x2)
+            .capability("files.read", 0x4)
+            .capability("files.write", 0x8)
+            .build()
+            .unwrap();
+        DescriptorRegistry::builder()
+            .domain("server", server)
+            .domain("channel", channel)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_qualified_names() {
+        let registry = create_test_registry();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.resolve("server:Ban"), Ok(("server", 0x2)));
+        assert_eq!(registry.resolve("channel:files.*"), Ok(("channel", 0xC)));
+        assert_eq!(
+            registry.resolve("Ban"),
+            Err(RegistryError::MissingDomain {
+                name: "Ban".to_string()
+            })
+        );
+        assert_eq!(
+            registry.resolve("billing:Refund"),
+            Err(RegistryError::UnknownDomain {
+                domain: "billing".to_string()
+            })
+        );
+        assert_eq!(
+            registry.resolve("server:Send"),
+            Err(RegistryError::UnknownCapability {
+                domain: "server".to_string(),
+                name: "Send".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let registry = create_test_registry();
+
+        let permissions = registry
+            .encode(["server:Kick", "channel:Send", "channel:files.read"])
+            .unwrap();
+        assert_eq!(permissions.get("server"), Some(&0x1));
+        assert_eq!(permissions.get("channel"), Some(&0x6));
+        assert_eq!(
+            registry.decode(&permissions),
+            vec!["channel:Send", "channel:files.read", "server:Kick"]
+        );
+        assert!(registry.has_capability(&permissions, "channel:Send"));
+        assert!(!registry.has_capability(&permissions, "channel:files.*"));
+        assert!(!registry.has_capability(&permissions, "billing:Refund"));
+        assert!(!registry.has_capability(&permissions, "Send"));
+
+        let error = registry.encode(["server:Kick", "Send"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "name \"Send\" is not qualified with a domain"
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trips_bypass_roles() {
+        let server = CapabilityDescriptor::builder()
+            .capability("Kick", 0x1)
+            .capability("Admin", 0x2)
+            .grants_all("Admin")
+            .build()
+            .unwrap();
+        let registry = DescriptorRegistry::builder()
+            .domain("server", server)
+            .build()
+            .unwrap();
+
+        let permissions = registry.encode(["server:Admin"]).unwrap();
+        let names = registry.decode(&permissions);
+        assert_eq!(names, vec!["server:Admin"]);
+        assert_eq!(registry.encode(&names), Ok(permissions));
+    }
+
+    #[test]
+    fn test_encode_keeps_the_highest_level() {
+        let files = CapabilityDescriptor::builder()
+            .capability("Share", 0x1)
+            .level_field("Access", 0x6, ["none", "read", "write", "admin"])
+            .build()
+            .unwrap();
+        let registry = DescriptorRegistry::builder()
+            .domain("files", files)
+            .build()
+            .unwrap();
+
+        let permissions = registry
+            .encode(["files:Access=read", "files:Access=write", "files:Share"])
+            .unwrap();
+        assert_eq!(permissions.get("files"), Some(&0x5));
+        assert_eq!(
+            registry.decode(&permissions),
+            vec!["files:Share", "files:Access=write"]
+        );
+    }
+
+    #[test]
+    fn test_domain_permissions() {
+        let mut permissions = DomainPermissions::new();
+        assert!(permissions.is_empty());
+
+        permissions.set("server", 0x3);
+        permissions.set("channel", 0x1);
+        assert_eq!(
+            permissions.iter().collect::<Vec<_>>(),
+            vec![
+                (&"channel".to_string(), &0x1),
+                (&"server".to_string(), &0x3)
+            ]
+        );
+
+        permissions.set("server", 0x0);
+        assert_eq!(permissions.get("server"), None);
+
+        let registry = create_test_registry();
+        assert_eq!(
+            registry
+                .role(&permissions, "server")
+                .map(|role| role.hex_value),
+            Some(0x0)
+        );
+        assert!(registry.role(&permissions, "billing").is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let registry = create_test_registry();
+
+        let mut permissions = DomainPermissions::new();
+        permissions.set("server", 0x3);
+        assert_eq!(registry.validate(&permissions), Ok(()));
+
+        permissions.set("server", 0x10);
+        match registry.validate(&permissions) {
+            Err(RegistryError::InvalidValue { domain, error }) => {
+                assert_eq!(domain, "server");
+                assert_eq!(error.rule(), ValidationRule::UnknownBits);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut permissions = DomainPermissions::new();
+        permissions.set("billing", 0x1);
+        assert_eq!(
+            registry.validate(&permissions),
+            Err(RegistryError::UnknownDomain {
+                domain: "billing".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_domains() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .build()
+            .unwrap();
+
+        let cases = [
+            (
+                DescriptorRegistry::builder()
+                    .domain("server", descriptor.clone())
+                    .domain("server", descriptor.clone()),
+                RegistryError::DuplicateDomain {
+                    domain: "server".to_string(),
+                },
+            ),
+            (
+                DescriptorRegistry::builder().domain("a:b", descriptor.clone()),
+                RegistryError::InvalidDomainName {
+                    domain: "a:b".to_string(),
+                },
+            ),
+            (
+                DescriptorRegistry::builder()
+                    .domain("", descriptor.clone())
+                    .domain("server", descriptor.clone())
+                    .domain("server", descriptor),
+                RegistryError::InvalidDomainName {
+                    domain: String::new(),
+                },
+            ),
+        ];
+
+        for (builder, expected) in cases {
+            assert_eq!(builder.build().unwrap_err(), expected);
+        }
+    }
+}
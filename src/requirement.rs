@@ -0,0 +1,620 @@
+//! # Requirement Module
+//!
+//! This module parses access requirements such as `"Admin OR (Read AND Write) AND NOT Banned"`
+//! into a [`Requirement`], checked against a descriptor up front, and evaluates them against
+//! roles. A failed evaluation reports the sub-clause that was not met.
+//!
+//! ## Syntax
+//!
+//! - A capability name, composite or `Field=level` of a level field
+//! - `NOT x`, `x AND y`, `x OR y`, from the tightest binding to the loosest
+//! - Parentheses for grouping
+//!
+//! The keywords are upper case; every other run of characters other than whitespace and
+//! parentheses is a name.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     models::CapabilityDescriptor,
+//!     requirement::Requirement,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::builder()
+//!     .capability("Read", 0x1)
+//!     .capability("Write", 0x2)
+//!     .capability("Admin", 0x4)
+//!     .capability("Banned", 0x8)
+//!     .build()
+//!     .unwrap();
+//!
+//! let requirement = Requirement::parse("Admin OR (Read AND Write) AND NOT Banned", &descriptor).unwrap();
+//! assert_eq!(requirement.to_string(), "Admin OR (Read AND Write AND NOT Banned)");
+//!
+//! let editor = RoleCapability::new(descriptor.clone(), 0x3);
+//! assert!(requirement.evaluate(&editor).is_ok());
+//!
+//! let banned_editor = RoleCapability::new(descriptor, 0xB);
+//! let failure = requirement.evaluate(&banned_editor).unwrap_err();
+//! assert_eq!(
+//!     failure.to_string(),
+//!     "Admin OR (Read AND Write AND NOT Banned) is not met: Admin is not met; NOT Banned is not met"
+//! );
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::models::{CapabilityDescriptor, CapabilityName};
+use crate::role_capability::{CapabilityMatch, RoleCapability};
+use crate::value::PermissionValue;
+
+/// A parsed access requirement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// The named capability must be held.
+    Capability(CapabilityName),
+    /// The inner requirement must not be met.
+    Not(Box<Requirement>),
+    /// Every requirement must be met.
+    All(Vec<Requirement>),
+    /// At least one requirement must be met.
+    Any(Vec<Requirement>),
+}
+
+impl Requirement {
+    /// Parses a requirement and checks every name against a descriptor.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequirementError`] with the position of the bad input if the syntax is
+    /// invalid or a name is not defined in the descriptor.
+    pub fn parse<V: PermissionValue>(
+        input: &str,
+        descriptor: &CapabilityDescriptor<V>,
+    ) -> Result<Self, RequirementError> {
+        let tokens = tokenize(input);
+        if tokens.is_empty() {
+            return Err(RequirementError::new(
+                RequirementErrorKind::Empty,
+                input.len()..input.len(),
+            ));
+        }
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end: input.len(),
+            known: &|name: &str| descriptor.resolve(name).is_some(),
+        };
+        let requirement = parser.parse_or()?;
+        match parser.tokens.get(parser.next) {
+            Some(token) => Err(RequirementError::new(
+                RequirementErrorKind::UnexpectedToken {
+                    token: token.text.clone(),
+                },
+                token.span.clone(),
+            )),
+            None => Ok(requirement),
+        }
+    }
+
+    /// Evaluates the requirement against a role, matching capabilities like
+    /// [`has_capability`](RoleCapability::has_capability).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequirementFailure`] describing the sub-clause that was not met.
+    pub fn evaluate<V: PermissionValue>(
+        &self,
+        role: &RoleCapability<V>,
+    ) -> Result<(), RequirementFailure> {
+        self.evaluate_with(role, CapabilityMatch::Direct)
+    }
+
+    /// Evaluates the requirement against a role with the given match mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequirementFailure`] describing the sub-clause that was not met.
+    pub fn evaluate_with<V: PermissionValue>(
+        &self,
+        role: &RoleCapability<V>,
+        mode: CapabilityMatch,
+    ) -> Result<(), RequirementFailure> {
+        let failed = || RequirementFailure {
+            clause: self.clone(),
+            causes: Vec::new(),
+        };
+        match self {
+            Requirement::Capability(name) => {
+                if role.has_capability_with(name, mode) {
+                    Ok(())
+                } else {
+                    Err(failed())
+                }
+            }
+            Requirement::Not(inner) => match inner.evaluate_with(role, mode) {
+                Ok(()) => Err(failed()),
+                Err(_) => Ok(()),
+            },
+            Requirement::All(requirements) => requirements
+                .iter()
+                .try_for_each(|requirement| requirement.evaluate_with(role, mode)),
+            Requirement::Any(requirements) => {
+                let mut causes = Vec::new();
+                for requirement in requirements {
+                    match requirement.evaluate_with(role, mode) {
+                        Ok(()) => return Ok(()),
+                        Err(cause) => causes.push(cause),
+                    }
+                }
+                Err(RequirementFailure {
+                    clause: self.clone(),
+                    causes,
+                })
+            }
+        }
+    }
+
+    /// Returns `true` if the role meets the requirement.
+    pub fn is_satisfied_by<V: PermissionValue>(&self, role: &RoleCapability<V>) -> bool {
+        self.evaluate(role).is_ok()
+    }
+
+    fn write_operand(&self, f: &mut fmt::Formatter<'_>, parent: &Requirement) -> fmt::Result {
+        let needs_parentheses = matches!(
+            (parent, self),
+            (
+                Requirement::Not(_),
+                Requirement::All(_) | Requirement::Any(_)
+            ) | (Requirement::All(_), Requirement::Any(_))
+                | (Requirement::Any(_), Requirement::All(_))
+        );
+        if needs_parentheses {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+/// Renders the requirement in the syntax accepted by [`Requirement::parse`], adding
+/// parentheses around nested `AND` and `OR` groups.
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (requirements, separator) = match self {
+            Requirement::Capability(name) => return write!(f, "{}", name),
+            Requirement::Not(inner) => {
+                write!(f, "NOT ")?;
+                return inner.write_operand(f, self);
+            }
+            Requirement::All(requirements) => (requirements, " AND "),
+            Requirement::Any(requirements) => (requirements, " OR "),
+        };
+        for (index, requirement) in requirements.iter().enumerate() {
+            if index > 0 {
+                f.write_str(separator)?;
+            }
+            requirement.write_operand(f, self)?;
+        }
+        Ok(())
+    }
+}
+
+/// A requirement clause that a role did not meet.
+///
+/// An `AND` fails with the failure of its first unmet operand. An `OR` fails with itself
+/// as the clause and the failure of every alternative as `causes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementFailure {
+    /// The clause that was not met.
+    pub clause: Requirement,
+    /// Why each alternative of an `OR` clause was not met. Empty for other clauses.
+    pub causes: Vec<RequirementFailure>,
+}
+
+impl fmt::Display for RequirementFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not met", self.clause)?;
+        if self.causes.is_empty() {
+            return Ok(());
+        }
+        let mut leaves = Vec::new();
+        self.collect_leaves(&mut leaves);
+        let leaves: Vec<String> = leaves
+            .into_iter()
+            .map(|leaf| format!("{} is not met", leaf.clause))
+            .collect();
+        write!(f, ": {}", leaves.join("; "))
+    }
+}
+
+impl RequirementFailure {
+    fn collect_leaves<'a>(&'a self, leaves: &mut Vec<&'a RequirementFailure>) {
+        if self.causes.is_empty() {
+            leaves.push(self);
+        }
+        for cause in &self.causes {
+            cause.collect_leaves(leaves);
+        }
+    }
+}
+
+impl std::error::Error for RequirementFailure {}
+
+/// The reason a requirement could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequirementErrorKind {
+    /// The input is empty or only whitespace.
+    Empty,
+    /// The input ended where a name, `NOT` or `(` was expected.
+    UnexpectedEnd,
+    /// A token appears where it is not allowed.
+    UnexpectedToken { token: String },
+    /// A `(` is never closed.
+    UnclosedParenthesis,
+    /// A name is not defined in the descriptor.
+    UnknownCapability { name: String },
+}
+
+impl fmt::Display for RequirementErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequirementErrorKind::Empty => write!(f, "empty requirement"),
+            RequirementErrorKind::UnexpectedEnd => write!(f, "unexpected end of requirement"),
+            RequirementErrorKind::UnexpectedToken { token } => {
+                write!(f, "unexpected {:?}", token)
+            }
+            RequirementErrorKind::UnclosedParenthesis => write!(f, "unclosed parenthesis"),
+            RequirementErrorKind::UnknownCapability { name } => {
+                write!(f, "unknown capability {:?}", name)
+            }
+        }
+    }
+}
+
+/// Error returned when a requirement cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementError {
+    kind: RequirementErrorKind,
+    span: Range<usize>,
+}
+
+impl RequirementError {
+    fn new(kind: RequirementErrorKind, span: Range<usize>) -> Self {
+        RequirementError { kind, span }
+    }
+
+    /// Returns the reason for the failure.
+    pub fn kind(&self) -> &RequirementErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte offset in the input where the problem starts.
+    pub fn position(&self) -> usize {
+        self.span.start
+    }
+
+    /// Returns the byte range of the offending input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for RequirementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.span.start)
+    }
+}
+
+impl std::error::Error for RequirementError {}
+
+struct Token {
+    text: String,
+    span: Range<usize>,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        if c != '(' && c != ')' {
+            while let Some(&(index, next)) = chars.peek() {
+                if next.is_whitespace() || next == '(' || next == ')' {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+        }
+        tokens.push(Token {
+            text: input[start..end].to_string(),
+            span: start..end,
+        });
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    next: usize,
+    end: usize,
+    known: &'a dyn Fn(&str) -> bool,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.next).map(|token| token.text.as_str())
+    }
+
+    fn parse_or(&mut self) -> Result<Requirement, RequirementError> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek() == Some("OR") {
+            self.next += 1;
+            operands.push(self.parse_and()?);
+        }
+        Ok(combine(operands, Requirement::Any))
+    }
+
+    fn parse_and(&mut self) -> Result<Requirement, RequirementError> {
+        let mut operands = vec![self.parse_unary()?];
+        while self.peek() == Some("AND") {
+            self.next += 1;
+            operands.push(self.parse_unary()?);
+        }
+        Ok(combine(operands, Requirement::All))
+    }
+
+    fn parse_unary(&mut self) -> Result<Requirement, RequirementError> {
+        let Some(token) = self.tokens.get(self.next) else {
+            return Err(RequirementError::new(
+                RequirementErrorKind::UnexpectedEnd,
+                self.end..self.end,
+            ));
+        };
+        let span = token.span.clone();
+        match token.text.as_str() {
+            "NOT" => {
+                self.next += 1;
+                Ok(Requirement::Not(Box::new(self.parse_unary()?)))
+            }
+            "(" => {
+                self.next += 1;
+                let inner = self.parse_or()?;
+                if self.peek() != Some(")") {
+                    return Err(RequirementError::new(
+                        RequirementErrorKind::UnclosedParenthesis,
+                        span,
+                    ));
+                }
+                self.next += 1;
+                Ok(inner)
+            }
+            ")" | "AND" | "OR" => Err(RequirementError::new(
+                RequirementErrorKind::UnexpectedToken {
+                    token: token.text.clone(),
+                },
+                span,
+            )),
+            name => {
+                if !(self.known)(name) {
+                    return Err(RequirementError::new(
+                        RequirementErrorKind::UnknownCapability {
+                            name: name.to_string(),
+                        },
+                        span,
+                    ));
+                }
+                self.next += 1;
+                Ok(Requirement::Capability(name.to_string()))
+            }
+        }
+    }
+}
+
+/// Joins operands of one operator, flattening operands that use the same operator.
+fn combine(
+    mut operands: Vec<Requirement>,
+    operator: fn(Vec<Requirement>) -> Requirement,
+) -> Requirement {
+    if operands.len() == 1 {
+        return operands.remove(0);
+    }
+    let mut flattened = Vec::new();
+    for operand in operands {
+        match (operator(Vec::new()), operand) {
+            (Requirement::All(_), Requirement::All(inner))
+            | (Requirement::Any(_), Requirement::Any(inner)) => flattened.extend(inner),
+            (_, operand) => flattened.push(operand),
+        }
+    }
+    operator(flattened)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_descriptor() -> CapabilityDescriptor {
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Admin", 0x4)
+            .capability("Banned", 0x8)
+            .composite("ReadWrite", 0x3)
+            .level_field("Support", 0x30, ["none", "agent", "lead"])
+            .build()
+            .unwrap()
+    }
+
+    fn parse(input: &str) -> Requirement {
+        Requirement::parse(input, &create_test_descriptor()).unwrap()
+    }
+
+    fn capability(name: &str) -> Requirement {
+        Requirement::Capability(name.to_string())
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(
+            parse("Admin OR Read AND NOT Banned"),
+            Requirement::Any(vec![
+                capability("Admin"),
+                Requirement::All(vec![
+                    capability("Read"),
+                    Requirement::Not(Box::new(capability("Banned"))),
+                ]),
+            ])
+        );
+        assert_eq!(
+            parse("(Admin OR Read) AND Write"),
+            Requirement::All(vec![
+                Requirement::Any(vec![capability("Admin"), capability("Read")]),
+                capability("Write"),
+            ])
+        );
+        assert_eq!(
+            parse("Read AND (Write AND Admin)"),
+            Requirement::All(vec![
+                capability("Read"),
+                capability("Write"),
+                capability("Admin")
+            ])
+        );
+        assert_eq!(parse("((Read))"), capability("Read"));
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for input in [
+            "Read",
+            "NOT (Read OR Write)",
+            "Admin OR (Read AND Write AND NOT Banned)",
+            "(Admin OR Read) AND NOT NOT Write",
+            "ReadWrite AND Support=lead",
+        ] {
+            assert_eq!(parse(input).to_string(), input);
+            assert_eq!(parse(&parse(input).to_string()), parse(input));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let descriptor = create_test_descriptor();
+        let cases = [
+            ("", RequirementErrorKind::Empty, 0),
+            ("   ", RequirementErrorKind::Empty, 3),
+            ("Read AND", RequirementErrorKind::UnexpectedEnd, 8),
+            (
+                "Read Write",
+                RequirementErrorKind::UnexpectedToken {
+                    token: "Write".to_string(),
+                },
+                5,
+            ),
+            (
+                "OR Read",
+                RequirementErrorKind::UnexpectedToken {
+                    token: "OR".to_string(),
+                },
+                0,
+            ),
+            (
+                "Read AND (Write",
+                RequirementErrorKind::UnclosedParenthesis,
+                9,
+            ),
+            (
+                "Read)",
+                RequirementErrorKind::UnexpectedToken {
+                    token: ")".to_string(),
+                },
+                4,
+            ),
+            (
+                "Read OR Fly",
+                RequirementErrorKind::UnknownCapability {
+                    name: "Fly".to_string(),
+                },
+                8,
+            ),
+            (
+                "Support=owner",
+                RequirementErrorKind::UnknownCapability {
+                    name: "Support=owner".to_string(),
+                },
+                0,
+            ),
+        ];
+
+        for (input, kind, position) in cases {
+            let error = Requirement::parse(input, &descriptor).unwrap_err();
+            assert_eq!(error.kind(), &kind, "input {:?}", input);
+            assert_eq!(error.position(), position, "input {:?}", input);
+        }
+
+        let error = Requirement::parse("Read OR Fly", &descriptor).unwrap_err();
+        assert_eq!(&"Read OR Fly"[error.span()], "Fly");
+        assert_eq!(
+            error.to_string(),
+            "unknown capability \"Fly\" at position 8"
+        );
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let descriptor = create_test_descriptor();
+        let requirement = parse("Admin OR (Read AND Write) AND NOT Banned");
+        let role = |value: i32| RoleCapability::new(descriptor.clone(), value);
+
+        assert!(requirement.is_satisfied_by(&role(0x4)));
+        assert!(requirement.is_satisfied_by(&role(0xC)));
+        assert!(requirement.is_satisfied_by(&role(0x3)));
+        assert!(!requirement.is_satisfied_by(&role(0xB)));
+        assert!(!requirement.is_satisfied_by(&role(0x1)));
+    }
+
+    #[test]
+    fn test_failure_reports_sub_clause() {
+        let descriptor = create_test_descriptor();
+        let role = RoleCapability::new(descriptor, 0x9);
+
+        let failure = parse("Read AND Write AND Admin")
+            .evaluate(&role)
+            .unwrap_err();
+        assert_eq!(failure.clause, capability("Write"));
+        assert_eq!(failure.to_string(), "Write is not met");
+
+        let failure = parse("Read AND NOT Banned").evaluate(&role).unwrap_err();
+        assert_eq!(
+            failure.clause,
+            Requirement::Not(Box::new(capability("Banned")))
+        );
+
+        let failure = parse("Admin OR Write").evaluate(&role).unwrap_err();
+        assert_eq!(failure.causes.len(), 2);
+        assert_eq!(
+            failure.to_string(),
+            "Admin OR Write is not met: Admin is not met; Write is not met"
+        );
+    }
+
+    #[test]
+    fn test_match_modes() {
+        let descriptor = create_test_descriptor();
+        let reader = RoleCapability::new(descriptor, 0x11);
+        let requirement = parse("ReadWrite AND Support=agent");
+
+        assert!(requirement.evaluate(&reader).is_ok());
+        assert!(requirement
+            .evaluate_with(&reader, CapabilityMatch::AllBits)
+            .is_err());
+    }
+}
@@ -4,9 +4,14 @@
 //! The [`RoleCapability`] struct combines a capability descriptor with a permission value
 //! to provide methods for extracting and checking individual capabilities.
 
+use crate::format::{format_value, type_width, Radix};
+use crate::level::{split_level, union_levels};
 use crate::models::{
     CapabilityDescriptor, CapabilityHexUnitSet, CapabilityName, CapabilityNameSet, CapilityHexValue,
 };
+use crate::namespace::split_wildcard;
+use crate::value::PermissionValue;
+use std::fmt;
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
@@ -30,10 +35,12 @@ use js_sys;
 /// };
 ///
 /// // Create a capability descriptor
-/// let mut descriptor = CapabilityDescriptor::new();
-/// descriptor.insert("Administrator".to_string(), 0x1);
-/// descriptor.insert("ManageServer".to_string(), 0x2);
-/// descriptor.insert("ManageRoles".to_string(), 0x4);
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Administrator", 0x1)
+///     .capability("ManageServer", 0x2)
+///     .capability("ManageRoles", 0x4)
+///     .build()
+///     .unwrap();
 ///
 /// // Create a role with combined permissions (Administrator + ManageServer)
 /// let permission_value: CapilityHexValue = 0x3;
@@ -49,12 +56,18 @@ use js_sys;
 ///
 /// * `descriptor` - The capability descriptor defining available permissions
 /// * `hex_value` - The combined hexadecimal permission value for this role
-pub struct RoleCapability {
-    descriptor: CapabilityDescriptor,
-    pub hex_value: CapilityHexValue,
+///
+/// # Value Types
+///
+/// The value type `V` defaults to [`CapilityHexValue`] and can be any
+/// [`PermissionValue`], for example `u64` for permission systems with more than 31 bits.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RoleCapability<V = CapilityHexValue> {
+    descriptor: CapabilityDescriptor<V>,
+    pub hex_value: V,
 }
 
-impl RoleCapability {
+impl<V: PermissionValue> RoleCapability<V> {
     /// Creates a new `RoleCapability` instance.
     ///
     /// # Arguments
@@ -74,19 +87,26 @@ impl RoleCapability {
     ///     role_capability::RoleCapability,
     /// };
     ///
-    /// let mut descriptor = CapabilityDescriptor::new();
-    /// descriptor.insert("Read".to_string(), 0x1);
-    /// descriptor.insert("Write".to_string(), 0x2);
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .build()
+    ///     .unwrap();
     ///
     /// let role = RoleCapability::new(descriptor, 0x3); // Read + Write
     /// ```
-    pub fn new(descriptor: CapabilityDescriptor, hex_value: CapilityHexValue) -> Self {
+    pub fn new(descriptor: CapabilityDescriptor<V>, hex_value: V) -> Self {
         RoleCapability {
             descriptor,
             hex_value,
         }
     }
 
+    /// Returns the descriptor the role's value is interpreted against.
+    pub fn descriptor(&self) -> &CapabilityDescriptor<V> {
+        &self.descriptor
+    }
+
     /// Extracts individual capability hex values from the combined permission value.
     ///
     /// This method analyzes the role's permission value and returns a set containing
@@ -104,10 +124,12 @@ impl RoleCapability {
     ///     role_capability::RoleCapability,
     /// };
     ///
-    /// let mut descriptor = CapabilityDescriptor::new();
-    /// descriptor.insert("Read".to_string(), 0x1);
-    /// descriptor.insert("Write".to_string(), 0x2);
-    /// descriptor.insert("Execute".to_string(), 0x4);
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .capability("Execute", 0x4)
+    ///     .build()
+    ///     .unwrap();
     ///
     /// let role = RoleCapability::new(descriptor, 0x3); // Read + Write
     /// let hex_set = role.to_hex_set();
@@ -116,12 +138,11 @@ impl RoleCapability {
     /// assert!(hex_set.contains(&0x2)); // Write
     /// assert!(!hex_set.contains(&0x4)); // Execute not included
     /// ```
-    pub fn to_hex_set(&self) -> CapabilityHexUnitSet {
+    pub fn to_hex_set(&self) -> CapabilityHexUnitSet<V> {
         let mut hex_set = CapabilityHexUnitSet::new();
-        let mut tmp = self.descriptor.values();
-        while let Some(&value) = tmp.next() {
-            if self.hex_value & value != 0 {
-                hex_set.insert(value);
+        for value in self.descriptor.values() {
+            if self.hex_value.intersects(value) {
+                hex_set.insert(value.clone());
             }
         }
         hex_set
@@ -131,7 +152,11 @@ impl RoleCapability {
     ///
     /// This method analyzes the role's permission value and returns a set containing
     /// the names of all capabilities that are enabled for this role. This is the
-    /// most user-friendly representation of a role's permissions.
+    /// most user-friendly representation of a role's permissions. Only the bits actually
+    /// set are listed, so the names always encode back to the same value; see
+    /// [`to_granted_name_set`](RoleCapability::to_granted_name_set) for the capabilities
+    /// granted by bypasses. Composites are not listed; see
+    /// [`to_collapsed_name_set`](RoleCapability::to_collapsed_name_set).
     ///
     /// # Returns
     ///
@@ -145,10 +170,12 @@ impl RoleCapability {
     ///     role_capability::RoleCapability,
     /// };
     ///
-    /// let mut descriptor = CapabilityDescriptor::new();
-    /// descriptor.insert("Administrator".to_string(), 0x1);
-    /// descriptor.insert("ManageServer".to_string(), 0x2);
-    /// descriptor.insert("ManageRoles".to_string(), 0x4);
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Administrator", 0x1)
+    ///     .capability("ManageServer", 0x2)
+    ///     .capability("ManageRoles", 0x4)
+    ///     .build()
+    ///     .unwrap();
     ///
     /// let role = RoleCapability::new(descriptor, 0x3); // Administrator + ManageServer
     /// let name_set = role.to_name_set();
@@ -158,19 +185,468 @@ impl RoleCapability {
     /// assert!(!name_set.contains("ManageRoles"));
     /// ```
     pub fn to_name_set(&self) -> CapabilityNameSet {
+        self.names_in(&self.hex_value)
+    }
+
+    /// Extracts capability names like [`to_name_set`](RoleCapability::to_name_set), but
+    /// also includes every capability granted by a bypass the role holds.
+    ///
+    /// The result describes what the role can do rather than the bits it stores, so it
+    /// does not encode back to [`hex_value`](RoleCapability::hex_value) when a bypass is
+    /// held.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Administrator", 0x1)
+    ///     .capability("Read", 0x2)
+    ///     .grants_all("Administrator")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let admin = RoleCapability::new(descriptor, 0x1);
+    /// assert_eq!(admin.to_name_set().len(), 1);
+    /// assert!(admin.to_granted_name_set().contains("Read"));
+    /// ```
+    pub fn to_granted_name_set(&self) -> CapabilityNameSet {
+        self.names_in(&self.granted_value())
+    }
+
+    fn names_in(&self, value: &V) -> CapabilityNameSet {
         let mut name_set = CapabilityNameSet::new();
-        for (name, &value) in &self.descriptor {
-            if self.hex_value & value != 0 {
+        for (name, capability) in &self.descriptor {
+            if value.intersects(capability) {
                 name_set.insert(name.clone());
             }
         }
+        name_set.extend(self.level_names().into_iter().map(|(_, name)| name));
         name_set
     }
 
+    /// Extracts capability names like [`to_name_set`](RoleCapability::to_name_set), but
+    /// replaces capabilities by the composites that cover them where possible.
+    ///
+    /// Composites are tried from the widest to the narrowest, then by name. A composite is
+    /// used only if every one of its bits is held and none of them is already covered by
+    /// another composite in the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .capability("Execute", 0x4)
+    ///     .composite("ReadWrite", 0x3)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let role = RoleCapability::new(descriptor, 0x7);
+    /// let names = role.to_collapsed_name_set();
+    /// assert_eq!(names.len(), 2);
+    /// assert!(names.contains("ReadWrite"));
+    /// assert!(names.contains("Execute"));
+    /// ```
+    pub fn to_collapsed_name_set(&self) -> CapabilityNameSet {
+        let mut composites: Vec<(&CapabilityName, &V)> = self.descriptor.composites().collect();
+        composites.sort_by(|a, b| b.1.count_ones().cmp(&a.1.count_ones()).then(a.0.cmp(b.0)));
+
+        let mut remaining = self.hex_value.clone();
+        let mut name_set = CapabilityNameSet::new();
+        for (name, value) in composites {
+            if remaining.contains_all(value) {
+                remaining = remaining.and_not(value);
+                name_set.insert(name.clone());
+            }
+        }
+        for (name, value) in &self.descriptor {
+            if remaining.intersects(value) {
+                name_set.insert(name.clone());
+            }
+        }
+        name_set.extend(self.level_names().into_iter().map(|(_, name)| name));
+        name_set
+    }
+
+    /// Returns the names of the enabled capabilities as a list in a stable order.
+    ///
+    /// Unlike [`to_name_set`](RoleCapability::to_name_set), the result is the same on every
+    /// run, which makes it suitable for display and snapshot tests. Level fields set to a
+    /// non-zero level are listed as `Field=level`.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - Whether to sort by bit position or by name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::{CapabilityOrder, RoleCapability},
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Write", 0x1)
+    ///     .capability("Read", 0x2)
+    ///     .capability("Admin", 0x8)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let role = RoleCapability::new(descriptor, 0xB);
+    /// assert_eq!(
+    ///     role.to_name_list(CapabilityOrder::BitPosition),
+    ///     vec!["Write", "Read", "Admin"]
+    /// );
+    /// assert_eq!(
+    ///     role.to_name_list(CapabilityOrder::Name),
+    ///     vec!["Admin", "Read", "Write"]
+    /// );
+    /// ```
+    pub fn to_name_list(&self, order: CapabilityOrder) -> Vec<CapabilityName> {
+        let mut names: Vec<(Option<u32>, CapabilityName)> = self
+            .descriptor
+            .sorted_entries()
+            .into_iter()
+            .filter(|(_, value)| self.hex_value.intersects(value))
+            .map(|(name, value)| (value.bit_positions().first().copied(), name.clone()))
+            .collect();
+        names.extend(
+            self.level_names()
+                .into_iter()
+                .map(|(bit, name)| (Some(bit), name)),
+        );
+        match order {
+            CapabilityOrder::BitPosition => names.sort(),
+            CapabilityOrder::Name => names.sort_by(|a, b| a.1.cmp(&b.1)),
+        }
+        names.into_iter().map(|(_, name)| name).collect()
+    }
+
+    /// Returns the hex values of the enabled capabilities, ordered by bit position.
+    ///
+    /// This is the ordered counterpart of [`to_hex_set`](RoleCapability::to_hex_set).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .capability("Admin", 0x8)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let role = RoleCapability::new(descriptor, 0xB);
+    /// assert_eq!(role.to_hex_list(), vec![0x1, 0x2, 0x8]);
+    /// ```
+    pub fn to_hex_list(&self) -> Vec<V> {
+        self.descriptor
+            .sorted_entries()
+            .into_iter()
+            .filter(|(_, value)| self.hex_value.intersects(value))
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    /// Formats the permission value as zero-padded hexadecimal, such as `0x0000000B`.
+    ///
+    /// The value is padded to the width of the value type. For
+    /// [`CapabilityBitSet`](crate::bitset::CapabilityBitSet) values it is padded to the
+    /// highest bit of the descriptor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .capability("Admin", 0x8)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let role = RoleCapability::new(descriptor, 0xB);
+    /// assert_eq!(role.to_hex_string(), "0x0000000B");
+    /// assert_eq!(role.to_binary_string(), "0b00000000000000000000000000001011");
+    /// assert_eq!(role.to_octal_string(), "0o00000000013");
+    /// ```
+    pub fn to_hex_string(&self) -> String {
+        format_value(&self.hex_value, Radix::Hex, self.value_width())
+    }
+
+    /// Formats the permission value as zero-padded binary, such as `0b00001011`.
+    ///
+    /// Padding follows the same rule as [`to_hex_string`](RoleCapability::to_hex_string).
+    pub fn to_binary_string(&self) -> String {
+        format_value(&self.hex_value, Radix::Binary, self.value_width())
+    }
+
+    /// Formats the permission value as zero-padded octal, such as `0o00000000013`.
+    ///
+    /// Padding follows the same rule as [`to_hex_string`](RoleCapability::to_hex_string).
+    pub fn to_octal_string(&self) -> String {
+        format_value(&self.hex_value, Radix::Octal, self.value_width())
+    }
+
+    /// Renders one row per capability, showing its bit, value and whether the role has it.
+    ///
+    /// Rows are ordered by bit position. Bits that are set in the value but not defined
+    /// in the descriptor are listed as `(undefined)`. Intended for CLI output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let role = RoleCapability::new(descriptor, 0x5);
+    /// let table = role.bit_table();
+    /// let lines: Vec<&str> = table.lines().collect();
+    ///
+    /// assert_eq!(lines[0], "Bit  Value       Capability   Set");
+    /// assert_eq!(lines[1], "  0  0x00000001  Read         yes");
+    /// assert_eq!(lines[2], "  1  0x00000002  Write        no");
+    /// assert_eq!(lines[3], "  2  0x00000004  (undefined)  yes");
+    /// ```
+    pub fn bit_table(&self) -> String {
+        let width = self.value_width();
+        let mut rows: Vec<(u32, String, &str, bool)> = Vec::new();
+        let mut defined = V::zero();
+        for (name, value) in self.descriptor.sorted_entries() {
+            defined = defined.or(value);
+            let bit = value.bit_positions().first().copied().unwrap_or(0);
+            rows.push((
+                bit,
+                format_value(value, Radix::Hex, width),
+                name.as_str(),
+                self.hex_value.intersects(value),
+            ));
+        }
+        for field in self.descriptor.level_fields() {
+            defined = defined.or(field.mask());
+            for bit in field.mask().bit_positions() {
+                if let Some(value) = V::bit(bit) {
+                    rows.push((
+                        bit,
+                        format_value(&value, Radix::Hex, width),
+                        field.name().as_str(),
+                        self.hex_value.test_bit(bit),
+                    ));
+                }
+            }
+        }
+        for bit in self.hex_value.and_not(&defined).bit_positions() {
+            if let Some(value) = V::bit(bit) {
+                rows.push((
+                    bit,
+                    format_value(&value, Radix::Hex, width),
+                    "(undefined)",
+                    true,
+                ));
+            }
+        }
+        rows.sort_by_key(|row| row.0);
+
+        let bit_width = rows
+            .iter()
+            .map(|row| row.0.to_string().len())
+            .chain(std::iter::once("Bit".len()))
+            .max()
+            .unwrap_or(0);
+        let value_width = rows
+            .iter()
+            .map(|row| row.1.len())
+            .chain(std::iter::once("Value".len()))
+            .max()
+            .unwrap_or(0);
+        let name_width = rows
+            .iter()
+            .map(|row| row.2.len())
+            .chain(std::iter::once("Capability".len()))
+            .max()
+            .unwrap_or(0);
+
+        let mut table = format!(
+            "{:<bit_width$}  {:<value_width$}  {:<name_width$}  Set\n",
+            "Bit", "Value", "Capability"
+        );
+        for (bit, value, name, set) in rows {
+            table.push_str(&format!(
+                "{:>bit_width$}  {:<value_width$}  {:<name_width$}  {}\n",
+                bit,
+                value,
+                name,
+                if set { "yes" } else { "no" }
+            ));
+        }
+        table
+    }
+
+    /// Returns the number of bits to pad formatted values to.
+    fn value_width(&self) -> u32 {
+        let descriptor_bits = self
+            .descriptor
+            .values()
+            .map(|value| value.bit_len())
+            .max()
+            .unwrap_or(0);
+        type_width::<V>(descriptor_bits)
+    }
+
+    /// Encodes capability names back into a combined permission value.
+    ///
+    /// This is the reverse of [`to_name_set`](RoleCapability::to_name_set): the values of
+    /// all named capabilities are combined with a bitwise OR, and a level field named more
+    /// than once holds the highest of its levels. Encoding is strict; if any name is not
+    /// defined in the descriptor, nothing is encoded and every unknown name is reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnknownCapabilityError`] listing the unknown names, in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .capability("Admin", 0x8)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let value = RoleCapability::encode_names(&descriptor, ["Read", "Write"]).unwrap();
+    /// assert_eq!(value, 0x3);
+    ///
+    /// let error = RoleCapability::encode_names(&descriptor, ["Read", "Fly"]).unwrap_err();
+    /// assert_eq!(error.names, vec!["Fly".to_string()]);
+    /// ```
+    pub fn encode_names<I, S>(
+        descriptor: &CapabilityDescriptor<V>,
+        names: I,
+    ) -> Result<V, UnknownCapabilityError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let encoded = Self::encode_names_lenient(descriptor, names);
+        if encoded.unknown.is_empty() {
+            Ok(encoded.hex_value)
+        } else {
+            Err(UnknownCapabilityError {
+                names: encoded.unknown,
+            })
+        }
+    }
+
+    /// Encodes capability names into a permission value, skipping unknown names.
+    ///
+    /// Names that are not defined in the descriptor are left out of the value and
+    /// returned separately in [`NameEncoding::unknown`], so callers can warn about them
+    /// without rejecting the whole list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let encoded = RoleCapability::encode_names_lenient(&descriptor, ["Write", "Fly"]);
+    /// assert_eq!(encoded.hex_value, 0x2);
+    /// assert_eq!(encoded.unknown, vec!["Fly".to_string()]);
+    /// ```
+    pub fn encode_names_lenient<I, S>(
+        descriptor: &CapabilityDescriptor<V>,
+        names: I,
+    ) -> NameEncoding<V>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut hex_value = V::zero();
+        let mut unknown: Vec<CapabilityName> = Vec::new();
+        for name in names {
+            let name = name.as_ref();
+            match descriptor.resolve(name) {
+                Some(value) => {
+                    hex_value = union_levels(descriptor.level_fields(), &hex_value, &value)
+                }
+                None => {
+                    if !unknown.iter().any(|seen| seen == name) {
+                        unknown.push(name.to_string());
+                    }
+                }
+            }
+        }
+        NameEncoding { hex_value, unknown }
+    }
+
+    /// Creates a role from a list of capability names.
+    ///
+    /// Shorthand for [`encode_names`](RoleCapability::encode_names) followed by
+    /// [`new`](RoleCapability::new).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UnknownCapabilityError`] if any name is not defined in the descriptor.
+    pub fn from_names<I, S>(
+        descriptor: CapabilityDescriptor<V>,
+        names: I,
+    ) -> Result<Self, UnknownCapabilityError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let hex_value = Self::encode_names(&descriptor, names)?;
+        Ok(RoleCapability::new(descriptor, hex_value))
+    }
+
     /// Checks if the role has a specific capability.
     ///
     /// This method determines whether the role's permission value includes
-    /// the specified capability by checking if the corresponding bit is set.
+    /// the specified capability by checking if the corresponding bit is set, or
+    /// whether a bypass capability the role holds grants it.
     ///
     /// # Arguments
     ///
@@ -189,9 +665,11 @@ impl RoleCapability {
     ///     role_capability::RoleCapability,
     /// };
     ///
-    /// let mut descriptor = CapabilityDescriptor::new();
-    /// descriptor.insert("Read".to_string(), 0x1);
-    /// descriptor.insert("Write".to_string(), 0x2);
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .build()
+    ///     .unwrap();
     ///
     /// let role = RoleCapability::new(descriptor, 0x1); // Read only
     ///
@@ -205,10 +683,467 @@ impl RoleCapability {
     /// This method performs a HashMap lookup followed by a bitwise AND operation,
     /// making it O(1) average case complexity.
     pub fn has_capability(&self, permission_name: &CapabilityName) -> bool {
-        if let Some(&value) = self.descriptor.get(permission_name) {
-            return self.hex_value & value != 0;
+        self.has_capability_with(permission_name, CapabilityMatch::Direct)
+    }
+
+    /// Checks if the role has a specific capability, using the given match mode.
+    ///
+    /// [`CapabilityMatch::Direct`] behaves like
+    /// [`has_capability`](RoleCapability::has_capability). [`CapabilityMatch::Implied`]
+    /// also accepts capabilities implied, directly or transitively, by the ones the role
+    /// holds or is granted by a bypass. [`CapabilityMatch::AllBits`] requires every bit of
+    /// a composite to be held, where the other modes accept any of them.
+    ///
+    /// A `Field=level` name matches when the level field holds exactly that level, and a
+    /// `namespace.*` wildcard when every capability of the namespace is held, in every
+    /// mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::{CapabilityMatch, RoleCapability},
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .implies("Write", ["Read"])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let writer = RoleCapability::new(descriptor, 0x2);
+    /// assert!(!writer.has_capability_with("Read", CapabilityMatch::Direct));
+    /// assert!(writer.has_capability_with("Read", CapabilityMatch::Implied));
+    /// ```
+    pub fn has_capability_with(&self, name: &str, mode: CapabilityMatch) -> bool {
+        let (value, all_bits) = if let Some(value) = self.descriptor.get(name) {
+            (value.clone(), mode == CapabilityMatch::AllBits)
+        } else if let Some(value) =
+            split_wildcard(name).and_then(|namespace| self.descriptor.namespace(namespace))
+        {
+            (value, true)
+        } else {
+            return split_level(name)
+                .is_some_and(|(field, level)| self.level(field).is_some_and(|held| held == level));
+        };
+        let held = match mode {
+            CapabilityMatch::Implied => self.descriptor.implied_by(&self.granted_value()),
+            CapabilityMatch::Direct | CapabilityMatch::AllBits => self.granted_value(),
+        };
+        if all_bits {
+            held.contains_all(&value)
+        } else {
+            held.intersects(&value)
         }
-        false
+    }
+
+    /// Returns a role holding this role's capabilities and every capability they imply,
+    /// directly or transitively.
+    ///
+    /// Bypasses are not expanded into the value; they are still honoured by the queries of
+    /// the returned role.
+    pub fn expand(&self) -> Self {
+        RoleCapability::new(
+            self.descriptor.clone(),
+            self.descriptor.implied_by(&self.hex_value),
+        )
+    }
+
+    /// Returns the permission value together with every capability granted by the
+    /// bypass capabilities it holds.
+    ///
+    /// Equal to [`hex_value`](RoleCapability::hex_value) when the descriptor defines no
+    /// bypasses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::RoleCapability,
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Administrator", 0x1)
+    ///     .capability("Read", 0x2)
+    ///     .capability("Write", 0x4)
+    ///     .grants_all("Administrator")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let admin = RoleCapability::new(descriptor, 0x1);
+    /// assert_eq!(admin.hex_value, 0x1);
+    /// assert_eq!(admin.granted_value(), 0x7);
+    /// ```
+    pub fn granted_value(&self) -> V {
+        self.hex_value
+            .or(&self.descriptor.granted_by(&self.hex_value))
+    }
+}
+
+/// Formats the role as its padded hex value followed by its capabilities in bit order,
+/// for example `0x0000000B [Read, Write, Admin]`.
+impl<V: PermissionValue> fmt::Display for RoleCapability<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}]",
+            self.to_hex_string(),
+            self.to_name_list(CapabilityOrder::BitPosition).join(", ")
+        )
+    }
+}
+
+impl<V: PermissionValue> fmt::Debug for RoleCapability<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoleCapability")
+            .field("hex_value", &format_args!("{}", self.to_hex_string()))
+            .field(
+                "capabilities",
+                &self.to_name_list(CapabilityOrder::BitPosition),
+            )
+            .finish()
+    }
+}
+
+/// How [`RoleCapability::has_capability_with`] decides whether a capability is held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapabilityMatch {
+    /// The capability's bit is set, or a held bypass grants it.
+    #[default]
+    Direct,
+    /// As `Direct`, or a held capability implies it.
+    Implied,
+    /// Every bit of the capability is set or granted by a held bypass. Only differs from
+    /// `Direct` for composites.
+    AllBits,
+}
+
+/// Sort order for [`RoleCapability::to_name_list`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapabilityOrder {
+    /// Lowest bit first.
+    #[default]
+    BitPosition,
+    /// Alphabetical by capability name.
+    Name,
+}
+
+/// Result of [`RoleCapability::encode_names_lenient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameEncoding<V = CapilityHexValue> {
+    /// Combined value of every known name.
+    pub hex_value: V,
+    /// Names not defined in the descriptor, in input order and without duplicates.
+    pub unknown: Vec<CapabilityName>,
+}
+
+/// Error returned when names cannot be encoded because the descriptor does not define them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCapabilityError {
+    /// The unknown names, in input order and without duplicates.
+    pub names: Vec<CapabilityName>,
+}
+
+impl fmt::Display for UnknownCapabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.names.len() == 1 {
+            "capability"
+        } else {
+            "capabilities"
+        };
+        write!(f, "unknown {}: {}", noun, self.names.join(", "))
+    }
+}
+
+impl std::error::Error for UnknownCapabilityError {}
+
+/// How a role value is written when serialized.
+///
+/// Deserialization accepts all three forms regardless of the format used to write them.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueFormat {
+    /// A zero-padded hex string, such as `"0x0000000B"`.
+    #[default]
+    Hex,
+    /// A plain integer, such as `11`. Uses the value type's own serde representation, so a
+    /// [`CapabilityBitSet`](crate::bitset::CapabilityBitSet) is still written as a string.
+    Integer,
+    /// A list of capability names in bit order, such as `["Read", "Write", "Admin"]`.
+    Names,
+}
+
+#[cfg(feature = "serde")]
+impl<V: PermissionValue> RoleCapability<V> {
+    /// Returns a serializable view of the role that writes its value in the given format.
+    ///
+    /// The role is written as a `descriptor` map and a `value`. Serializing the role
+    /// directly uses [`ValueFormat::Hex`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::{RoleCapability, ValueFormat},
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .build()
+    ///     .unwrap();
+    /// let role = RoleCapability::new(descriptor, 0x3);
+    ///
+    /// let json = serde_json::to_string(&role.serialize_as(ValueFormat::Names)).unwrap();
+    /// assert_eq!(
+    ///     json,
+    ///     r#"{"descriptor":{"Read":1,"Write":2},"value":["Read","Write"]}"#
+    /// );
+    ///
+    /// let back: RoleCapability = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(back.hex_value, 0x3);
+    /// ```
+    pub fn serialize_as(&self, format: ValueFormat) -> FormattedRole<'_, V> {
+        FormattedRole { role: self, format }
+    }
+
+    /// Returns a serializable view of the role value alone, in the given format.
+    ///
+    /// Use this to store roles in configuration files next to a shared descriptor, and
+    /// [`ValueSeed`] to read them back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use permission_translation::{
+    ///     models::CapabilityDescriptor,
+    ///     role_capability::{RoleCapability, ValueFormat},
+    /// };
+    ///
+    /// let descriptor = CapabilityDescriptor::builder()
+    ///     .capability("Read", 0x1)
+    ///     .capability("Write", 0x2)
+    ///     .build()
+    ///     .unwrap();
+    /// let role = RoleCapability::new(descriptor, 0x3);
+    ///
+    /// assert_eq!(serde_json::to_string(&role.value_as(ValueFormat::Hex)).unwrap(), r#""0x00000003""#);
+    /// assert_eq!(serde_json::to_string(&role.value_as(ValueFormat::Integer)).unwrap(), "3");
+    /// ```
+    pub fn value_as(&self, format: ValueFormat) -> FormattedValue<'_, V> {
+        FormattedValue { role: self, format }
+    }
+
+    /// Deserializes a role value in any [`ValueFormat`] and pairs it with `descriptor`.
+    ///
+    /// Strings are read with [`parse_value`](crate::parse::parse_value), so hex, decimal,
+    /// binary and `"Read|Write"` forms are all accepted. Values are checked against the
+    /// descriptor and unknown names are rejected.
+    pub fn deserialize_value<'de, D>(
+        descriptor: CapabilityDescriptor<V>,
+        deserializer: D,
+    ) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::DeserializeSeed;
+
+        let hex_value = ValueSeed::new(&descriptor).deserialize(deserializer)?;
+        Ok(RoleCapability::new(descriptor, hex_value))
+    }
+}
+
+/// Serializable view of a role returned by [`RoleCapability::serialize_as`].
+#[cfg(feature = "serde")]
+pub struct FormattedRole<'a, V> {
+    role: &'a RoleCapability<V>,
+    format: ValueFormat,
+}
+
+#[cfg(feature = "serde")]
+impl<V: PermissionValue + serde::Serialize> serde::Serialize for FormattedRole<'_, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("RoleCapability", 2)?;
+        state.serialize_field("descriptor", &self.role.descriptor)?;
+        state.serialize_field("value", &self.role.value_as(self.format))?;
+        state.end()
+    }
+}
+
+/// Serializable view of a role value returned by [`RoleCapability::value_as`].
+#[cfg(feature = "serde")]
+pub struct FormattedValue<'a, V> {
+    role: &'a RoleCapability<V>,
+    format: ValueFormat,
+}
+
+#[cfg(feature = "serde")]
+impl<V: PermissionValue + serde::Serialize> serde::Serialize for FormattedValue<'_, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.format {
+            ValueFormat::Hex => serializer.serialize_str(&self.role.to_hex_string()),
+            ValueFormat::Integer => self.role.hex_value.serialize(serializer),
+            ValueFormat::Names => {
+                serializer.collect_seq(self.role.to_name_list(CapabilityOrder::BitPosition))
+            }
+        }
+    }
+}
+
+/// Serializes as a `descriptor` map and a hex string `value`.
+#[cfg(feature = "serde")]
+impl<V: PermissionValue + serde::Serialize> serde::Serialize for RoleCapability<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_as(ValueFormat::Hex).serialize(serializer)
+    }
+}
+
+/// Deserializes from a `descriptor` map and a `value` in any [`ValueFormat`].
+#[cfg(feature = "serde")]
+impl<'de, V: PermissionValue + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for RoleCapability<V>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(bound = "V: PermissionValue + serde::Deserialize<'de>")]
+        struct RoleFields<V: PermissionValue> {
+            descriptor: CapabilityDescriptor<V>,
+            value: RawValue,
+        }
+
+        let fields = RoleFields::<V>::deserialize(deserializer)?;
+        let hex_value = fields
+            .value
+            .resolve(&fields.descriptor)
+            .map_err(serde::de::Error::custom)?;
+        Ok(RoleCapability::new(fields.descriptor, hex_value))
+    }
+}
+
+/// Reads a role value in any [`ValueFormat`] and checks it against a descriptor.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::{models::CapabilityDescriptor, role_capability::ValueSeed};
+/// use serde::de::DeserializeSeed;
+///
+/// let descriptor = CapabilityDescriptor::builder()
+///     .capability("Read", 0x1)
+///     .capability("Write", 0x2)
+///     .build()
+///     .unwrap();
+///
+/// let mut json = serde_json::Deserializer::from_str(r#"["Read", "Write"]"#);
+/// assert_eq!(ValueSeed::new(&descriptor).deserialize(&mut json).unwrap(), 0x3);
+/// ```
+#[cfg(feature = "serde")]
+pub struct ValueSeed<'a, V> {
+    descriptor: &'a CapabilityDescriptor<V>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, V> ValueSeed<'a, V> {
+    /// Creates a seed that resolves values against `descriptor`.
+    pub fn new(descriptor: &'a CapabilityDescriptor<V>) -> Self {
+        ValueSeed { descriptor }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, V: PermissionValue> serde::de::DeserializeSeed<'de> for ValueSeed<'_, V> {
+    type Value = V;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<V, D::Error> {
+        use serde::Deserialize;
+
+        RawValue::deserialize(deserializer)?
+            .resolve(self.descriptor)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A role value as read from the input, before the descriptor is known.
+#[cfg(feature = "serde")]
+enum RawValue {
+    /// A number or string, resolved with `parse_value`.
+    Text(String),
+    /// A list of capability names.
+    Names(Vec<String>),
+}
+
+#[cfg(feature = "serde")]
+impl RawValue {
+    fn resolve<V: PermissionValue>(
+        self,
+        descriptor: &CapabilityDescriptor<V>,
+    ) -> Result<V, String> {
+        match self {
+            RawValue::Text(text) => {
+                crate::parse::parse_value(&text, descriptor).map_err(|e| e.to_string())
+            }
+            RawValue::Names(names) => {
+                RoleCapability::encode_names(descriptor, names).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RawValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RawValueVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for RawValueVisitor {
+            type Value = RawValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "an integer, a value string or a list of capability names"
+                )
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<RawValue, E> {
+                Ok(RawValue::Text(v.to_string()))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<RawValue, E> {
+                Ok(RawValue::Text(v.to_string()))
+            }
+
+            fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<RawValue, E> {
+                Ok(RawValue::Text(v.to_string()))
+            }
+
+            fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<RawValue, E> {
+                Ok(RawValue::Text(v.to_string()))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<RawValue, E> {
+                Ok(RawValue::Text(v.to_string()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<RawValue, A::Error> {
+                let mut names = Vec::new();
+                while let Some(name) = seq.next_element::<String>()? {
+                    names.push(name);
+                }
+                Ok(RawValue::Names(names))
+            }
+        }
+
+        deserializer.deserialize_any(RawValueVisitor)
     }
 }
 
@@ -218,12 +1153,13 @@ mod tests {
     use crate::models::CapabilityDescriptor;
 
     fn create_test_descriptor() -> CapabilityDescriptor {
-        let mut descriptor = CapabilityDescriptor::new();
-        descriptor.insert("Read".to_string(), 0x1);
-        descriptor.insert("Write".to_string(), 0x2);
-        descriptor.insert("Execute".to_string(), 0x4);
-        descriptor.insert("Admin".to_string(), 0x8);
-        descriptor
+        CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .build()
+            .unwrap()
     }
 
     #[test]
@@ -304,7 +1240,7 @@ mod tests {
         assert!(name_set.contains("Admin"));
 
         // Verify all capabilities from descriptor are present
-        for (capability_name, _) in &descriptor {
+        for capability_name in descriptor.names() {
             assert!(name_set.contains(capability_name));
         }
     }
@@ -338,7 +1274,7 @@ mod tests {
         let descriptor = create_test_descriptor();
         let role = RoleCapability::new(descriptor.clone(), 0xF); // All permissions
 
-        for (capability_name, _) in &descriptor {
+        for capability_name in descriptor.names() {
             assert!(role.has_capability(capability_name));
         }
 
@@ -395,11 +1331,309 @@ mod tests {
     }
 
     #[test]
-    fn test_large_permission_system() {
-        let mut large_descriptor = CapabilityDescriptor::new();
-        for i in 0..10 {
-            large_descriptor.insert(format!("Permission{}", i), 1 << i);
+    fn test_bypass_capabilities() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .capability("Runner", 0x10)
+            .grants_all("Admin")
+            .grants("Runner", ["Read", "Execute"])
+            .build()
+            .unwrap();
+
+        let admin = RoleCapability::new(descriptor.clone(), 0x8);
+        assert_eq!(admin.hex_value, 0x8);
+        assert_eq!(admin.granted_value(), 0x1F);
+        assert!(admin.has_capability(&"Write".to_string()));
+        assert_eq!(admin.to_granted_name_set().len(), 5);
+        // The name and hex views describe the raw value only
+        assert_eq!(
+            admin.to_name_list(CapabilityOrder::BitPosition),
+            vec!["Admin"]
+        );
+        assert_eq!(admin.to_string(), "0x00000008 [Admin]");
+        assert_eq!(admin.to_hex_list(), vec![0x8]);
+
+        let runner = RoleCapability::new(descriptor.clone(), 0x12);
+        assert!(runner.has_capability(&"Read".to_string()));
+        assert!(runner.has_capability(&"Execute".to_string()));
+        assert!(!runner.has_capability(&"Admin".to_string()));
+        let mut granted: Vec<String> = runner.to_granted_name_set().into_iter().collect();
+        granted.sort();
+        assert_eq!(granted, vec!["Execute", "Read", "Runner", "Write"]);
+        assert_eq!(
+            runner.to_name_list(CapabilityOrder::Name),
+            vec!["Runner", "Write"]
+        );
+
+        let plain = RoleCapability::new(descriptor, 0x2);
+        assert_eq!(plain.granted_value(), 0x2);
+        assert!(!plain.has_capability(&"Read".to_string()));
+    }
+
+    #[test]
+    fn test_implied_match_and_expand() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .capability("Deploy", 0x10)
+            .implies("Write", ["Read"])
+            .implies("Deploy", ["Write", "Execute"])
+            .grants("Admin", ["Deploy"])
+            .build()
+            .unwrap();
+
+        let deployer = RoleCapability::new(descriptor.clone(), 0x10);
+        for name in ["Read", "Write", "Execute"] {
+            assert!(!deployer.has_capability(&name.to_string()));
+            assert!(deployer.has_capability_with(name, CapabilityMatch::Implied));
+        }
+        assert!(!deployer.has_capability_with("Admin", CapabilityMatch::Implied));
+        assert!(!deployer.has_capability_with("Missing", CapabilityMatch::Implied));
+        assert_eq!(deployer.expand().hex_value, 0x17);
+
+        // Capabilities granted by a bypass are expanded too
+        let admin = RoleCapability::new(descriptor, 0x8);
+        assert!(admin.has_capability_with("Deploy", CapabilityMatch::Direct));
+        assert!(!admin.has_capability_with("Read", CapabilityMatch::Direct));
+        assert!(admin.has_capability_with("Read", CapabilityMatch::Implied));
+        assert_eq!(admin.expand().hex_value, 0x8);
+    }
+
+    #[test]
+    fn test_composite_matching_and_collapsing() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .composite("ReadWrite", 0x3)
+            .composite("WriteExecute", 0x6)
+            .composite("Everything", 0xF)
+            .build()
+            .unwrap();
+
+        let reader = RoleCapability::new(descriptor.clone(), 0x1);
+        assert!(reader.has_capability(&"ReadWrite".to_string()));
+        assert!(!reader.has_capability_with("ReadWrite", CapabilityMatch::AllBits));
+        assert!(reader.has_capability_with("Read", CapabilityMatch::AllBits));
+
+        let names = |value: i32| {
+            let mut names: Vec<CapabilityName> = RoleCapability::new(descriptor.clone(), value)
+                .to_collapsed_name_set()
+                .into_iter()
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(0x3), vec!["ReadWrite"]);
+        assert_eq!(names(0x7), vec!["Execute", "ReadWrite"]);
+        assert_eq!(names(0xE), vec!["Admin", "WriteExecute"]);
+        assert_eq!(names(0xF), vec!["Everything"]);
+        assert_eq!(names(0x5), vec!["Execute", "Read"]);
+        assert!(names(0x0).is_empty());
+
+        // The plain name set never lists composites
+        let all = RoleCapability::new(descriptor.clone(), 0xF);
+        assert_eq!(all.to_name_set().len(), 4);
+        assert!(all.has_capability_with("Everything", CapabilityMatch::AllBits));
+
+        // Composites resolve when encoding names
+        assert_eq!(
+            RoleCapability::encode_names(&descriptor, ["ReadWrite", "Admin"]),
+            Ok(0xB)
+        );
+    }
+
+    #[test]
+    fn test_to_name_list_bit_order() {
+        let descriptor = create_test_descriptor();
+        let role = RoleCapability::new(descriptor, 0xF);
+
+        // Same order on every call, lowest bit first
+        for _ in 0..10 {
+            assert_eq!(
+                role.to_name_list(CapabilityOrder::BitPosition),
+                vec!["Read", "Write", "Execute", "Admin"]
+            );
         }
+        assert_eq!(
+            role.to_name_list(CapabilityOrder::default()),
+            role.to_name_list(CapabilityOrder::BitPosition)
+        );
+    }
+
+    #[test]
+    fn test_to_name_list_name_order() {
+        let descriptor = create_test_descriptor();
+        let role = RoleCapability::new(descriptor, 0xD);
+
+        assert_eq!(
+            role.to_name_list(CapabilityOrder::Name),
+            vec!["Admin", "Execute", "Read"]
+        );
+    }
+
+    #[test]
+    fn test_to_hex_list() {
+        let descriptor = create_test_descriptor();
+
+        let role = RoleCapability::new(descriptor.clone(), 0xA);
+        assert_eq!(role.to_hex_list(), vec![0x2, 0x8]);
+
+        let empty_role = RoleCapability::new(descriptor, 0x0);
+        assert!(empty_role.to_hex_list().is_empty());
+        assert!(empty_role
+            .to_name_list(CapabilityOrder::BitPosition)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let descriptor = CapabilityDescriptor::builder()
+            .capability("Read", 0x1)
+            .capability("Write", 0x2)
+            .capability("Execute", 0x4)
+            .capability("Admin", 0x8)
+            .build()
+            .unwrap();
+
+        let role = RoleCapability::new(descriptor.clone(), 0xB);
+        assert_eq!(role.to_string(), "0x0000000B [Read, Write, Admin]");
+
+        let empty_role = RoleCapability::new(descriptor, 0x0);
+        assert_eq!(empty_role.to_string(), "0x00000000 []");
+    }
+
+    #[test]
+    fn test_debug() {
+        let role = RoleCapability::new(create_test_descriptor(), 0x3);
+        assert_eq!(
+            format!("{:?}", role),
+            "RoleCapability { hex_value: 0x00000003, capabilities: [\"Read\", \"Write\"] }"
+        );
+    }
+
+    #[test]
+    fn test_padded_formats() {
+        let role = RoleCapability::new(create_test_descriptor(), 0x9);
+        assert_eq!(role.to_hex_string(), "0x00000009");
+        assert_eq!(
+            role.to_binary_string(),
+            "0b00000000000000000000000000001001"
+        );
+        assert_eq!(role.to_octal_string(), "0o00000000011");
+
+        let wide = CapabilityDescriptor::<u64>::builder()
+            .capability("High", 1 << 40)
+            .build()
+            .unwrap();
+        let wide_role = RoleCapability::new(wide, 1 << 40);
+        assert_eq!(wide_role.to_hex_string(), "0x0000010000000000");
+        assert_eq!(wide_role.to_string(), "0x0000010000000000 [High]");
+    }
+
+    #[test]
+    fn test_bit_table() {
+        let role = RoleCapability::new(create_test_descriptor(), 0x19);
+        let table = role.bit_table();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "Bit  Value       Capability   Set");
+        assert_eq!(lines[1], "  0  0x00000001  Read         yes");
+        assert_eq!(lines[2], "  1  0x00000002  Write        no");
+        assert_eq!(lines[4], "  3  0x00000008  Admin        yes");
+        assert_eq!(lines[5], "  4  0x00000010  (undefined)  yes");
+    }
+
+    #[test]
+    fn test_encode_names() {
+        let descriptor = create_test_descriptor();
+
+        assert_eq!(
+            RoleCapability::encode_names(&descriptor, ["Read", "Write"]),
+            Ok(0x3)
+        );
+        assert_eq!(
+            RoleCapability::encode_names(&descriptor, Vec::<String>::new()),
+            Ok(0x0)
+        );
+        // Repeated names are harmless
+        assert_eq!(
+            RoleCapability::encode_names(&descriptor, ["Admin", "Admin"]),
+            Ok(0x8)
+        );
+    }
+
+    #[test]
+    fn test_encode_names_round_trip() {
+        let descriptor = create_test_descriptor();
+        let role = RoleCapability::new(descriptor.clone(), 0xD);
+
+        let encoded = RoleCapability::encode_names(&descriptor, role.to_name_set()).unwrap();
+        assert_eq!(encoded, 0xD);
+    }
+
+    #[test]
+    fn test_encode_names_strict_rejects_unknown() {
+        let descriptor = create_test_descriptor();
+
+        let error =
+            RoleCapability::encode_names(&descriptor, ["Read", "Fly", "Swim", "Fly"]).unwrap_err();
+        assert_eq!(error.names, vec!["Fly".to_string(), "Swim".to_string()]);
+        assert_eq!(error.to_string(), "unknown capabilities: Fly, Swim");
+
+        let error = RoleCapability::encode_names(&descriptor, ["Fly"]).unwrap_err();
+        assert_eq!(error.to_string(), "unknown capability: Fly");
+    }
+
+    #[test]
+    fn test_encode_names_lenient() {
+        let descriptor = create_test_descriptor();
+
+        let encoded = RoleCapability::encode_names_lenient(&descriptor, ["Execute", "Fly", "Read"]);
+        assert_eq!(encoded.hex_value, 0x5);
+        assert_eq!(encoded.unknown, vec!["Fly".to_string()]);
+
+        let encoded = RoleCapability::encode_names_lenient(&descriptor, ["Read"]);
+        assert!(encoded.unknown.is_empty());
+    }
+
+    #[test]
+    fn test_from_names() {
+        let role =
+            RoleCapability::from_names(create_test_descriptor(), ["Write", "Admin"]).unwrap();
+        assert_eq!(role.hex_value, 0xA);
+        assert!(role.has_capability(&"Admin".to_string()));
+
+        assert!(RoleCapability::from_names(create_test_descriptor(), ["Nope"]).is_err());
+    }
+
+    #[test]
+    fn test_encode_names_wide_values() {
+        let descriptor = CapabilityDescriptor::<u64>::builder()
+            .capability("Low", 0x1)
+            .capability("High", 1 << 60)
+            .build()
+            .unwrap();
+
+        let value = RoleCapability::encode_names(&descriptor, ["Low", "High"]).unwrap();
+        assert_eq!(value, (1 << 60) | 0x1);
+    }
+
+    #[test]
+    fn test_large_permission_system() {
+        let large_descriptor = (0..10)
+            .fold(CapabilityDescriptor::builder(), |builder, i| {
+                builder.capability(format!("Permission{}", i), 1 << i)
+            })
+            .build()
+            .unwrap();
 
         // Test alternating permissions: 0x155 = 101010101 binary
         let role = RoleCapability::new(large_descriptor.clone(), 0x155);
@@ -432,10 +1666,12 @@ mod tests {
 
     #[test]
     fn test_descriptor_with_custom_hex_values() {
-        let mut custom_descriptor = CapabilityDescriptor::new();
-        custom_descriptor.insert("CustomA".to_string(), 0x10);
-        custom_descriptor.insert("CustomB".to_string(), 0x20);
-        custom_descriptor.insert("CustomC".to_string(), 0x40);
+        let custom_descriptor = CapabilityDescriptor::builder()
+            .capability("CustomA", 0x10)
+            .capability("CustomB", 0x20)
+            .capability("CustomC", 0x40)
+            .build()
+            .unwrap();
 
         let role = RoleCapability::new(custom_descriptor, 0x30); // CustomA + CustomB
 
@@ -451,7 +1687,7 @@ mod tests {
 
     #[test]
     fn test_empty_descriptor() {
-        let empty_descriptor = CapabilityDescriptor::new();
+        let empty_descriptor = CapabilityDescriptor::default();
         let role = RoleCapability::new(empty_descriptor, 0x0);
 
         assert!(role.to_hex_set().is_empty());
@@ -475,10 +1711,25 @@ impl JsRoleCapability {
     pub fn new(
         descriptor: &crate::models::JsCapabilityDescriptor,
         hex_value: CapilityHexValue,
-    ) -> JsRoleCapability {
-        JsRoleCapability {
-            inner: RoleCapability::new(descriptor.inner.clone(), hex_value),
-        }
+    ) -> Result<JsRoleCapability, JsValue> {
+        let descriptor = CapabilityDescriptor::try_from(descriptor.inner.clone())
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(JsRoleCapability {
+            inner: RoleCapability::new(descriptor, hex_value),
+        })
+    }
+
+    /// Creates a role from capability names, failing on names the descriptor does not define.
+    #[wasm_bindgen]
+    pub fn from_names(
+        descriptor: &crate::models::JsCapabilityDescriptor,
+        names: Vec<String>,
+    ) -> Result<JsRoleCapability, JsValue> {
+        let descriptor = CapabilityDescriptor::try_from(descriptor.inner.clone())
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let inner = RoleCapability::from_names(descriptor, names)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(JsRoleCapability { inner })
     }
 
     #[wasm_bindgen(getter = hexValue)]
@@ -493,9 +1744,9 @@ impl JsRoleCapability {
 
     #[wasm_bindgen]
     pub fn get_capability_names(&self) -> js_sys::Array {
-        let name_set = self.inner.to_name_set();
+        let names = self.inner.to_name_list(CapabilityOrder::BitPosition);
         let array = js_sys::Array::new();
-        for name in name_set {
+        for name in names {
             array.push(&JsValue::from_str(&name));
         }
         array
@@ -503,9 +1754,9 @@ impl JsRoleCapability {
 
     #[wasm_bindgen]
     pub fn get_capability_hex_values(&self) -> js_sys::Array {
-        let hex_set = self.inner.to_hex_set();
+        let hex_values = self.inner.to_hex_list();
         let array = js_sys::Array::new();
-        for &hex_value in &hex_set {
+        for &hex_value in &hex_values {
             array.push(&JsValue::from_f64(hex_value as f64));
         }
         array
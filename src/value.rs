@@ -0,0 +1,255 @@
+//! # Value Module
+//!
+//! This module defines the [`PermissionValue`] trait, which abstracts over the integer
+//! types that can back a permission value. The rest of the library is generic over this
+//! trait, so descriptors and roles can use wider integers than the default `i32`.
+//!
+//! ## Supported Types
+//!
+//! - `i32`: The historical default, kept for backwards compatibility (31 usable bits)
+//! - `u32`: 32 usable bits, no sign bit
+//! - `u64`: 64 usable bits, enough for systems like Discord's permission set
+//! - `u128`: 128 usable bits
+//!
+//! ## Example
+//!
+//! ```rust
+//! use permission_translation::{
+//!     checks::is_valid_hex,
+//!     models::CapabilityDescriptor,
+//!     role_capability::RoleCapability,
+//! };
+//!
+//! let descriptor = CapabilityDescriptor::<u64>::builder()
+//!     .capability("ViewChannel", 1 << 10)
+//!     .capability("SendPolls", 1 << 49)
+//!     .build()
+//!     .unwrap();
+//!
+//! let value: u64 = (1 << 10) | (1 << 49);
+//! assert!(is_valid_hex(value, &descriptor));
+//!
+//! let role = RoleCapability::new(descriptor, value);
+//! assert!(role.has_capability(&"SendPolls".to_string()));
+//! ```
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// A type that can hold a combined permission value.
+///
+/// Implementors behave like a set of bit positions: individual capabilities are
+/// single bits and combined values are the bitwise OR of those bits. The trait
+/// only uses methods (rather than operator bounds) so that it can also be
+/// implemented by types that are not `Copy`.
+///
+/// # Examples
+///
+/// ```rust
+/// use permission_translation::value::PermissionValue;
+///
+/// let value: u64 = 0b1010;
+/// assert!(value.test_bit(1));
+/// assert!(!value.test_bit(2));
+/// assert_eq!(value.bit_positions(), vec![1, 3]);
+/// assert_eq!(u64::bit(40), Some(1u64 << 40));
+/// assert_eq!(u64::bit(64), None);
+/// ```
+pub trait PermissionValue: Clone + Eq + Ord + Hash + Debug + Default {
+    /// The number of bits the type can hold, or `None` if it can grow without limit.
+    const BITS: Option<u32>;
+
+    /// Returns the empty value (no capabilities).
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns a value with only the bit at `index` set, or `None` if the
+    /// index does not fit in the type.
+    fn bit(index: u32) -> Option<Self>;
+
+    /// Returns `true` if the bit at `index` is set.
+    fn test_bit(&self, index: u32) -> bool;
+
+    /// Returns the position of the highest set bit plus one, or `0` for the empty value.
+    fn bit_len(&self) -> u32;
+
+    /// Bitwise OR of two values.
+    fn or(&self, other: &Self) -> Self;
+
+    /// Bitwise AND of two values.
+    fn and(&self, other: &Self) -> Self;
+
+    /// Bits set in `self` but not in `other` (`self & !other`).
+    fn and_not(&self, other: &Self) -> Self;
+
+    /// Arithmetic addition, returning the wrapped result and whether it overflowed.
+    fn overflowing_add(&self, other: &Self) -> (Self, bool);
+
+    /// Parses a string of digits (without prefix) in the given radix.
+    fn from_str_radix(digits: &str, radix: u32) -> Option<Self>;
+
+    /// Returns `true` if the value is negative. Always `false` for unsigned types.
+    fn is_negative(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if no bit is set.
+    fn is_zero(&self) -> bool {
+        self.bit_len() == 0
+    }
+
+    /// Returns `true` if the two values share at least one bit.
+    fn intersects(&self, other: &Self) -> bool {
+        !self.and(other).is_zero()
+    }
+
+    /// Returns `true` if every bit of `other` is also set in `self`.
+    fn contains_all(&self, other: &Self) -> bool {
+        other.and_not(self).is_zero()
+    }
+
+    /// Arithmetic addition, returning `None` on overflow.
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (sum, false) => Some(sum),
+            (_, true) => None,
+        }
+    }
+
+    /// Returns the positions of all set bits, in ascending order.
+    fn bit_positions(&self) -> Vec<u32> {
+        (0..self.bit_len()).filter(|&i| self.test_bit(i)).collect()
+    }
+
+    /// Returns the number of set bits.
+    fn count_ones(&self) -> u32 {
+        self.bit_positions().len() as u32
+    }
+}
+
+macro_rules! impl_permission_value {
+    ($($int:ty => $unsigned:ty),* $(,)?) => {
+        $(
+            impl PermissionValue for $int {
+                const BITS: Option<u32> = Some(<$int>::BITS);
+
+                fn bit(index: u32) -> Option<Self> {
+                    (1 as $unsigned).checked_shl(index).map(|bit| bit as $int)
+                }
+
+                fn test_bit(&self, index: u32) -> bool {
+                    index < <$int>::BITS && (*self as $unsigned >> index) & 1 == 1
+                }
+
+                fn bit_len(&self) -> u32 {
+                    <$int>::BITS - (*self as $unsigned).leading_zeros()
+                }
+
+                fn or(&self, other: &Self) -> Self {
+                    self | other
+                }
+
+                fn and(&self, other: &Self) -> Self {
+                    self & other
+                }
+
+                fn and_not(&self, other: &Self) -> Self {
+                    self & !other
+                }
+
+                fn overflowing_add(&self, other: &Self) -> (Self, bool) {
+                    <$int>::overflowing_add(*self, *other)
+                }
+
+                fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+                    <$int>::from_str_radix(digits, radix)
+                        .ok()
+                        .or_else(|| <$unsigned>::from_str_radix(digits, radix).ok().map(|v| v as $int))
+                }
+
+                #[allow(unused_comparisons)]
+                fn is_negative(&self) -> bool {
+                    *self < 0
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn count_ones(&self) -> u32 {
+                    <$int>::count_ones(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_permission_value!(i32 => u32, u32 => u32, u64 => u64, u128 => u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_construction() {
+        assert_eq!(i32::bit(0), Some(0x1));
+        assert_eq!(i32::bit(31), Some(i32::MIN));
+        assert_eq!(i32::bit(32), None);
+        assert_eq!(u64::bit(63), Some(1u64 << 63));
+        assert_eq!(u128::bit(127), Some(1u128 << 127));
+        assert_eq!(u128::bit(128), None);
+    }
+
+    #[test]
+    fn test_bit_inspection() {
+        let value: u128 = (1 << 100) | 0x5;
+        assert!(value.test_bit(0));
+        assert!(!value.test_bit(1));
+        assert!(value.test_bit(100));
+        assert!(!value.test_bit(200));
+        assert_eq!(value.bit_len(), 101);
+        assert_eq!(value.bit_positions(), vec![0, 2, 100]);
+        assert_eq!(PermissionValue::count_ones(&value), 3);
+    }
+
+    #[test]
+    fn test_sign_handling() {
+        assert!((-1i32).is_negative());
+        assert!(!0x7FFFFFFFi32.is_negative());
+        assert_eq!((-1i32).bit_len(), 32);
+        assert!(!u32::MAX.is_negative());
+    }
+
+    #[test]
+    fn test_bitwise_helpers() {
+        let a: u64 = 0b1100;
+        let b: u64 = 0b0110;
+        assert_eq!(a.or(&b), 0b1110);
+        assert_eq!(a.and(&b), 0b0100);
+        assert_eq!(a.and_not(&b), 0b1000);
+        assert!(a.intersects(&b));
+        assert!(a.contains_all(&0b1000));
+        assert!(!a.contains_all(&b));
+    }
+
+    #[test]
+    fn test_overflow_detection() {
+        assert_eq!(PermissionValue::checked_add(&1u32, &2u32), Some(3));
+        assert_eq!(PermissionValue::checked_add(&i32::MAX, &1), None);
+        assert_eq!(PermissionValue::checked_add(&u64::MAX, &1), None);
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        assert_eq!(
+            <u64 as PermissionValue>::from_str_radix("1F", 16),
+            Some(0x1F)
+        );
+        assert_eq!(
+            <i32 as PermissionValue>::from_str_radix("80000000", 16),
+            Some(i32::MIN)
+        );
+        assert_eq!(<u32 as PermissionValue>::from_str_radix("zz", 16), None);
+    }
+}
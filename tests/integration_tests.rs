@@ -12,13 +12,15 @@ use permission_translation::{
 #[test]
 fn test_complete_permission_workflow() {
     // Setup: Create a realistic permission system for a chat server
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("SendMessage".to_string(), 0x1);
-    descriptor.insert("ManageChannel".to_string(), 0x2);
-    descriptor.insert("ManageServer".to_string(), 0x4);
-    descriptor.insert("Administrator".to_string(), 0x8);
-    descriptor.insert("BanMembers".to_string(), 0x10);
-    descriptor.insert("CreateInvites".to_string(), 0x20);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("SendMessage", 0x1)
+        .capability("ManageChannel", 0x2)
+        .capability("ManageServer", 0x4)
+        .capability("Administrator", 0x8)
+        .capability("BanMembers", 0x10)
+        .capability("CreateInvites", 0x20)
+        .build()
+        .unwrap();
 
     // Test 1: Validate different permission combinations
     let test_cases = vec![
@@ -76,7 +78,7 @@ fn test_complete_permission_workflow() {
     let admin_capabilities = admin.to_name_set();
     assert_eq!(admin_capabilities.len(), 6); // All 6 permissions
 
-    for (capability_name, _) in &descriptor {
+    for capability_name in descriptor.names() {
         assert!(admin.has_capability(capability_name));
     }
 
@@ -92,7 +94,7 @@ fn test_complete_permission_workflow() {
 #[test]
 fn test_edge_cases_and_boundary_conditions() {
     // Test 1: Empty descriptor
-    let empty_descriptor = CapabilityDescriptor::new();
+    let empty_descriptor = CapabilityDescriptor::default();
     assert!(is_valid_hex(0x0, &empty_descriptor));
     assert!(!is_valid_hex(0x1, &empty_descriptor));
     assert_eq!(get_max_hex_value_descriptor(&empty_descriptor), 0x0);
@@ -103,8 +105,10 @@ fn test_edge_cases_and_boundary_conditions() {
     assert!(!empty_role.has_capability(&"AnyPermission".to_string()));
 
     // Test 2: Single permission
-    let mut single_descriptor = CapabilityDescriptor::new();
-    single_descriptor.insert("OnlyPermission".to_string(), 0x8);
+    let single_descriptor = CapabilityDescriptor::builder()
+        .capability("OnlyPermission", 0x8)
+        .build()
+        .unwrap();
 
     assert!(is_valid_hex(0x0, &single_descriptor));
     assert!(is_valid_hex(0x8, &single_descriptor));
@@ -118,10 +122,12 @@ fn test_edge_cases_and_boundary_conditions() {
     assert!(!single_role.has_capability(&"NonExistent".to_string()));
 
     // Test 3: Maximum practical permission system (16 permissions)
-    let mut max_descriptor = CapabilityDescriptor::new();
-    for i in 0..16 {
-        max_descriptor.insert(format!("Permission{}", i), 1 << i);
-    }
+    let max_descriptor = (0..16)
+        .fold(CapabilityDescriptor::builder(), |builder, i| {
+            builder.capability(format!("Permission{}", i), 1 << i)
+        })
+        .build()
+        .unwrap();
 
     let max_value = get_max_hex_value_descriptor(&max_descriptor);
     assert_eq!(max_value, 0xFFFF); // 16 bits all set
@@ -151,16 +157,18 @@ fn test_edge_cases_and_boundary_conditions() {
 #[test]
 fn test_real_world_file_permissions() {
     // Simulate Unix-like file permissions
-    let mut file_descriptor = CapabilityDescriptor::new();
-    file_descriptor.insert("OwnerRead".to_string(), 0x100); // 256
-    file_descriptor.insert("OwnerWrite".to_string(), 0x80); // 128
-    file_descriptor.insert("OwnerExecute".to_string(), 0x40); // 64
-    file_descriptor.insert("GroupRead".to_string(), 0x20); // 32
-    file_descriptor.insert("GroupWrite".to_string(), 0x10); // 16
-    file_descriptor.insert("GroupExecute".to_string(), 0x8); // 8
-    file_descriptor.insert("OtherRead".to_string(), 0x4); // 4
-    file_descriptor.insert("OtherWrite".to_string(), 0x2); // 2
-    file_descriptor.insert("OtherExecute".to_string(), 0x1); // 1
+    let file_descriptor = CapabilityDescriptor::builder()
+        .capability("OwnerRead", 0x100) // 256
+        .capability("OwnerWrite", 0x80) // 128
+        .capability("OwnerExecute", 0x40) // 64
+        .capability("GroupRead", 0x20) // 32
+        .capability("GroupWrite", 0x10) // 16
+        .capability("GroupExecute", 0x8) // 8
+        .capability("OtherRead", 0x4) // 4
+        .capability("OtherWrite", 0x2) // 2
+        .capability("OtherExecute", 0x1) // 1
+        .build()
+        .unwrap();
 
     // Test common permission combinations
     let read_only_all = 0x124; // Owner, Group, Other read (256 + 32 + 4)
@@ -184,16 +192,18 @@ fn test_real_world_file_permissions() {
 #[test]
 fn test_api_access_control_system() {
     // Simulate API endpoint permissions
-    let mut api_descriptor = CapabilityDescriptor::new();
-    api_descriptor.insert("ReadUsers".to_string(), 0x1);
-    api_descriptor.insert("CreateUser".to_string(), 0x2);
-    api_descriptor.insert("UpdateUser".to_string(), 0x4);
-    api_descriptor.insert("DeleteUser".to_string(), 0x8);
-    api_descriptor.insert("ReadPosts".to_string(), 0x10);
-    api_descriptor.insert("CreatePost".to_string(), 0x20);
-    api_descriptor.insert("UpdatePost".to_string(), 0x40);
-    api_descriptor.insert("DeletePost".to_string(), 0x80);
-    api_descriptor.insert("AdminAccess".to_string(), 0x100);
+    let api_descriptor = CapabilityDescriptor::builder()
+        .capability("ReadUsers", 0x1)
+        .capability("CreateUser", 0x2)
+        .capability("UpdateUser", 0x4)
+        .capability("DeleteUser", 0x8)
+        .capability("ReadPosts", 0x10)
+        .capability("CreatePost", 0x20)
+        .capability("UpdatePost", 0x40)
+        .capability("DeletePost", 0x80)
+        .capability("AdminAccess", 0x100)
+        .build()
+        .unwrap();
 
     // Test different API user types
 
@@ -228,7 +238,7 @@ fn test_api_access_control_system() {
     let admin_permissions = get_max_hex_value_descriptor(&api_descriptor);
     let admin = RoleCapability::new(api_descriptor.clone(), admin_permissions);
 
-    for (capability_name, _) in &api_descriptor {
+    for capability_name in api_descriptor.names() {
         assert!(admin.has_capability(capability_name));
     }
 
@@ -240,10 +250,12 @@ fn test_api_access_control_system() {
 fn test_validation_consistency() {
     // Test that validation is consistent across different descriptor sizes
     for num_permissions in 1..=20 {
-        let mut descriptor = CapabilityDescriptor::new();
-        for i in 0..num_permissions {
-            descriptor.insert(format!("Perm{}", i), 1 << i);
-        }
+        let descriptor = (0..num_permissions)
+            .fold(CapabilityDescriptor::builder(), |builder, i| {
+                builder.capability(format!("Perm{}", i), 1 << i)
+            })
+            .build()
+            .unwrap();
 
         let max_value = get_max_hex_value_descriptor(&descriptor);
 
@@ -268,8 +280,10 @@ fn test_validation_consistency() {
 
 #[test]
 fn test_error_recovery_and_robustness() {
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("Valid".to_string(), 0x1);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Valid", 0x1)
+        .build()
+        .unwrap();
 
     // Test with very large invalid values
     assert!(!is_valid_hex(i32::MAX, &descriptor));
@@ -5,20 +5,23 @@
 
 use permission_translation::{
     checks::{get_max_hex_value_descriptor, is_valid_hex},
-    models::{CapabilityDescriptor, CapabilityHexUnitSet, CapabilityNameSet, CapilityHexValue},
+    models::{
+        CapabilityDescriptor, CapabilityHexUnitSet, CapabilityMap, CapabilityNameSet,
+        CapilityHexValue, DescriptorError,
+    },
     role_capability::RoleCapability,
 };
 
 #[test]
 fn test_library_public_api_availability() {
     // Test that all public types are accessible
-    let _descriptor: CapabilityDescriptor = CapabilityDescriptor::new();
+    let _descriptor: CapabilityDescriptor = CapabilityDescriptor::default();
     let _hex_value: CapilityHexValue = 0x0;
     let _hex_set: CapabilityHexUnitSet = CapabilityHexUnitSet::new();
     let _name_set: CapabilityNameSet = CapabilityNameSet::new();
 
     // Test that all public functions are accessible
-    let descriptor = CapabilityDescriptor::new();
+    let descriptor = CapabilityDescriptor::default();
     let _is_valid = is_valid_hex(0x0, &descriptor);
     let _max_value = get_max_hex_value_descriptor(&descriptor);
     let _role = RoleCapability::new(descriptor, 0x0);
@@ -27,10 +30,12 @@ fn test_library_public_api_availability() {
 #[test]
 fn test_documentation_example_from_lib_rs() {
     // This test verifies the main example from lib.rs documentation
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("Administrator".to_string(), 0x1);
-    descriptor.insert("ManageServer".to_string(), 0x2);
-    descriptor.insert("ManageRoles".to_string(), 0x4);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Administrator", 0x1)
+        .capability("ManageServer", 0x2)
+        .capability("ManageRoles", 0x4)
+        .build()
+        .unwrap();
 
     // Create a role with combined permissions
     let permission_value: CapilityHexValue = 0x3; // Administrator + ManageServer
@@ -49,11 +54,13 @@ fn test_documentation_example_from_lib_rs() {
 #[test]
 fn test_readme_quick_start_example() {
     // This test verifies the quick start example from README.md
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("Read".to_string(), 0x1);
-    descriptor.insert("Write".to_string(), 0x2);
-    descriptor.insert("Delete".to_string(), 0x4);
-    descriptor.insert("Admin".to_string(), 0x8);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Delete", 0x4)
+        .capability("Admin", 0x8)
+        .build()
+        .unwrap();
 
     // Create a role with combined permissions (Read + Write)
     let permission_value: CapilityHexValue = 0x3;
@@ -79,11 +86,13 @@ fn test_complete_workflow_chain() {
     // Test a complete workflow that chains all major operations
 
     // Step 1: Create descriptor
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("Feature1".to_string(), 0x1);
-    descriptor.insert("Feature2".to_string(), 0x2);
-    descriptor.insert("Feature3".to_string(), 0x4);
-    descriptor.insert("Feature4".to_string(), 0x8);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Feature1", 0x1)
+        .capability("Feature2", 0x2)
+        .capability("Feature3", 0x4)
+        .capability("Feature4", 0x8)
+        .build()
+        .unwrap();
 
     // Step 2: Calculate maximum possible value
     let max_value = get_max_hex_value_descriptor(&descriptor);
@@ -138,39 +147,55 @@ fn test_complete_workflow_chain() {
 #[test]
 fn test_error_handling_and_edge_cases() {
     // Test with empty strings
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("".to_string(), 0x1); // Empty capability name
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("", 0x1) // Empty capability name
+        .build()
+        .unwrap();
 
     let role = RoleCapability::new(descriptor.clone(), 0x1);
     assert!(role.has_capability(&"".to_string()));
     assert!(!role.has_capability(&"non-empty".to_string()));
 
-    // Test with zero values
-    let mut zero_descriptor = CapabilityDescriptor::new();
-    zero_descriptor.insert("ZeroPermission".to_string(), 0x0);
-
-    // Zero permission should be handled gracefully
-    assert!(is_valid_hex(0x0, &zero_descriptor));
-
-    let zero_role = RoleCapability::new(zero_descriptor.clone(), 0x0);
-    // This is tricky - a permission with value 0x0 means "always off" in bitwise logic
-    // So even with permission value 0x0, it won't match capability value 0x0
-    assert!(!zero_role.has_capability(&"ZeroPermission".to_string()));
-
-    // Test with duplicate capability names (HashMap will overwrite)
-    let mut duplicate_descriptor = CapabilityDescriptor::new();
-    duplicate_descriptor.insert("Duplicate".to_string(), 0x1);
-    duplicate_descriptor.insert("Duplicate".to_string(), 0x2); // Overwrites previous
-
-    assert_eq!(duplicate_descriptor.get("Duplicate"), Some(&0x2));
-    assert_eq!(duplicate_descriptor.len(), 1);
+    // Test with zero values: the builder rejects them outright
+    let zero_error = CapabilityDescriptor::builder()
+        .capability("ZeroPermission", 0x0)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        zero_error,
+        DescriptorError::ZeroValue {
+            name: "ZeroPermission".to_string()
+        }
+    );
+
+    // A raw map with a zero entry is still handled gracefully by the checks
+    let mut zero_map = CapabilityMap::new();
+    zero_map.insert("ZeroPermission".to_string(), 0x0);
+    assert!(is_valid_hex(0x0, &zero_map));
+    assert!(CapabilityDescriptor::try_from(zero_map).is_err());
+
+    // Test with duplicate capability names (rejected instead of overwritten)
+    let duplicate_error = CapabilityDescriptor::builder()
+        .capability("Duplicate", 0x1)
+        .capability("Duplicate", 0x2)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        duplicate_error,
+        DescriptorError::DuplicateName {
+            name: "Duplicate".to_string()
+        }
+    );
 }
 
 #[test]
 fn test_type_safety_and_consistency() {
     // Test that our type aliases maintain type safety
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("TestCapability".to_string(), 0x1);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("TestCapability", 0x1)
+        .build()
+        .unwrap();
 
     let role = RoleCapability::new(descriptor.clone(), 0x1);
 
@@ -202,11 +227,13 @@ fn test_type_safety_and_consistency() {
 #[test]
 fn test_permission_combining_patterns() {
     // Test common patterns for combining permissions
-    let mut descriptor = CapabilityDescriptor::new();
-    descriptor.insert("Create".to_string(), 0x1);
-    descriptor.insert("Read".to_string(), 0x2);
-    descriptor.insert("Update".to_string(), 0x4);
-    descriptor.insert("Delete".to_string(), 0x8);
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Create", 0x1)
+        .capability("Read", 0x2)
+        .capability("Update", 0x4)
+        .capability("Delete", 0x8)
+        .build()
+        .unwrap();
 
     // Test CRUD combinations
     let create_only = 0x1;
@@ -239,10 +266,12 @@ fn test_permission_combining_patterns() {
 #[test]
 fn test_performance_characteristics() {
     // Test with a reasonably large permission system to ensure performance is acceptable
-    let mut large_descriptor = CapabilityDescriptor::new();
-    for i in 0..20 {
-        large_descriptor.insert(format!("Permission{:02}", i), 1 << i);
-    }
+    let large_descriptor = (0..20)
+        .fold(CapabilityDescriptor::builder(), |builder, i| {
+            builder.capability(format!("Permission{:02}", i), 1 << i)
+        })
+        .build()
+        .unwrap();
 
     // This should complete quickly even with 20 permissions
     let max_value = get_max_hex_value_descriptor(&large_descriptor);
@@ -268,6 +297,7 @@ fn test_performance_characteristics() {
 }
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_library_version_compatibility() {
     // Test that the library interface is stable and works as expected
     // This test serves as a regression test for API changes
@@ -275,7 +305,7 @@ fn test_library_version_compatibility() {
     use permission_translation::*;
 
     // Test that all modules are accessible
-    let descriptor = models::CapabilityDescriptor::new();
+    let descriptor = models::CapabilityDescriptor::default();
     let _validation = checks::is_valid_hex(0x0, &descriptor);
     let _role = role_capability::RoleCapability::new(descriptor, 0x0);
 
@@ -283,3 +313,41 @@ fn test_library_version_compatibility() {
     // For now, ensure direct module access works
     assert!(true); // Placeholder - if this compiles, the API is accessible
 }
+
+#[test]
+fn test_wide_permission_values() {
+    // 64-bit descriptor with capabilities beyond the 31 bits of the default i32 type
+    let descriptor = CapabilityDescriptor::<u64>::builder()
+        .capability("ViewChannel", 1 << 10)
+        .capability("SendPolls", 1 << 49)
+        .capability("UseExternalApps", 1 << 50)
+        .build()
+        .unwrap();
+
+    let value: u64 = (1 << 10) | (1 << 49);
+    assert!(is_valid_hex(value, &descriptor));
+    assert!(!is_valid_hex(1 << 63, &descriptor));
+    assert_eq!(
+        get_max_hex_value_descriptor(&descriptor),
+        (1 << 10) | (1 << 49) | (1 << 50)
+    );
+
+    let role = RoleCapability::new(descriptor, value);
+    assert!(role.has_capability(&"SendPolls".to_string()));
+    assert!(!role.has_capability(&"UseExternalApps".to_string()));
+    assert_eq!(role.to_name_set().len(), 2);
+
+    // 128-bit descriptor using the top bit
+    let descriptor = CapabilityDescriptor::<u128>::builder()
+        .capability("Low", 0x1)
+        .capability("High", 1 << 127)
+        .build()
+        .unwrap();
+
+    assert!(is_valid_hex(1 << 127, &descriptor));
+    assert!(!is_valid_hex(1 << 100, &descriptor));
+
+    let role = RoleCapability::new(descriptor, 1 << 127);
+    assert!(role.has_capability(&"High".to_string()));
+    assert!(role.to_hex_set().contains(&(1 << 127)));
+}
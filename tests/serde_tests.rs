@@ -0,0 +1,343 @@
+//! Serde round-trip tests
+//!
+//! These tests store descriptors and roles in JSON, TOML and YAML and read them back.
+//! Run them with `cargo test --features serde`.
+
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use permission_translation::{
+    bitset::CapabilityBitSet,
+    effective::PermissionCalculator,
+    explain::Explanation,
+    models::CapabilityDescriptor,
+    overwrite::{OverwriteLayers, PermissionOverwrite},
+    registry::{DescriptorRegistry, DomainPermissions},
+    role_capability::{RoleCapability, ValueFormat, ValueSeed},
+};
+use serde::de::DeserializeSeed;
+
+fn create_test_descriptor() -> CapabilityDescriptor {
+    CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Execute", 0x4)
+        .capability("Admin", 0x8)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_descriptor_json_round_trip() {
+    let descriptor = create_test_descriptor();
+
+    let json = serde_json::to_string(&descriptor).unwrap();
+    // Entries are written in bit order
+    assert_eq!(json, r#"{"Read":1,"Write":2,"Execute":4,"Admin":8}"#);
+
+    let back: CapabilityDescriptor = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, descriptor);
+}
+
+#[test]
+fn test_descriptor_deserialization_is_validated() {
+    let overlapping = r#"{"Read":1,"ReadWrite":3}"#;
+    let error = serde_json::from_str::<CapabilityDescriptor>(overlapping).unwrap_err();
+    assert!(error.to_string().contains("ReadWrite"));
+
+    let zero = r#"{"Nothing":0}"#;
+    assert!(serde_json::from_str::<CapabilityDescriptor>(zero).is_err());
+}
+
+#[test]
+fn test_descriptor_rules_round_trip() {
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Admin", 0x4)
+        .capability("Approve", 0x8)
+        .capability("Submit", 0x10)
+        .level_field("FileAccess", 0x60, ["none", "read", "write"])
+        .composite("ReadWrite", 0x3)
+        .grants_all("Admin")
+        .implies("Write", ["Read"])
+        .requires("Approve", ["Read"])
+        .exclusive("Approve", "Submit")
+        .build()
+        .unwrap();
+
+    let json = serde_json::to_value(&descriptor).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "capabilities": {"Read": 1, "Write": 2, "Admin": 4, "Approve": 8, "Submit": 16},
+            "level_fields": [{"name": "FileAccess", "mask": 96, "levels": ["none", "read", "write"]}],
+            "composites": {"ReadWrite": 3},
+            "bypasses": {"Admin": "all"},
+            "implies": {"Write": ["Read"]},
+            "requires": {"Approve": ["Read"]},
+            "exclusive": [["Approve", "Submit"]],
+        })
+    );
+    let back: CapabilityDescriptor = serde_json::from_value(json).unwrap();
+    assert_eq!(back, descriptor);
+
+    let yaml = serde_yaml::to_string(&descriptor).unwrap();
+    let back: CapabilityDescriptor = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(back, descriptor);
+
+    // A role keeps its descriptor's bypass and exclusion rules
+    let admin = RoleCapability::new(descriptor, 0x1C);
+    let back: RoleCapability =
+        serde_json::from_str(&serde_json::to_string(&admin).unwrap()).unwrap();
+    assert_eq!(back, admin);
+    assert!(back.has_capability(&"Write".to_string()));
+    assert_eq!(back.exclusivity_violations().len(), 1);
+}
+
+#[test]
+fn test_descriptor_document_is_validated() {
+    let unknown_rule = r#"{"capabilities":{"Read":1},"bypasses":{"Fly":"all"}}"#;
+    let error = serde_json::from_str::<CapabilityDescriptor>(unknown_rule).unwrap_err();
+    assert!(error.to_string().contains("Fly"));
+
+    let grants =
+        r#"{"capabilities":{"Read":1,"Mod":2},"bypasses":{"Mod":{"capabilities":["Read"]}}}"#;
+    let descriptor: CapabilityDescriptor = serde_json::from_str(grants).unwrap();
+    assert_eq!(descriptor.bypass_grants("Mod"), Some(0x1));
+
+    let unknown_section = r#"{"capabilities":{"Read":1},"aliases":{}}"#;
+    assert!(serde_json::from_str::<CapabilityDescriptor>(unknown_section).is_err());
+}
+
+#[test]
+fn test_role_json_value_formats() {
+    let role = RoleCapability::new(create_test_descriptor(), 0xB);
+
+    let hex = serde_json::to_value(role.value_as(ValueFormat::Hex)).unwrap();
+    assert_eq!(hex, serde_json::json!("0x0000000B"));
+
+    let integer = serde_json::to_value(role.value_as(ValueFormat::Integer)).unwrap();
+    assert_eq!(integer, serde_json::json!(11));
+
+    let names = serde_json::to_value(role.value_as(ValueFormat::Names)).unwrap();
+    assert_eq!(names, serde_json::json!(["Read", "Write", "Admin"]));
+}
+
+#[test]
+fn test_role_json_round_trip_in_every_format() {
+    let role = RoleCapability::new(create_test_descriptor(), 0xB);
+
+    for format in [ValueFormat::Hex, ValueFormat::Integer, ValueFormat::Names] {
+        let json = serde_json::to_string(&role.serialize_as(format)).unwrap();
+        let back: RoleCapability = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.hex_value, 0xB, "format {:?}", format);
+    }
+
+    // Serializing the role directly uses the hex format
+    let json = serde_json::to_string(&role).unwrap();
+    assert!(json.contains(r#""value":"0x0000000B""#));
+}
+
+#[test]
+fn test_role_deserialization_rejects_bad_values() {
+    let unknown_name = r#"{"descriptor":{"Read":1},"value":["Read","Fly"]}"#;
+    let error = serde_json::from_str::<RoleCapability>(unknown_name).unwrap_err();
+    assert!(error.to_string().contains("Fly"));
+
+    let unknown_bit = r#"{"descriptor":{"Read":1},"value":"0x2"}"#;
+    assert!(serde_json::from_str::<RoleCapability>(unknown_bit).is_err());
+}
+
+#[test]
+fn test_role_value_field_order_does_not_matter() {
+    let json = r#"{"value":["Write"],"descriptor":{"Read":1,"Write":2}}"#;
+    let role: RoleCapability = serde_json::from_str(json).unwrap();
+    assert_eq!(role.hex_value, 0x2);
+}
+
+#[test]
+fn test_role_value_accepts_composites() {
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .composite("ReadWrite", 0x3)
+        .build()
+        .unwrap();
+
+    let seed_value = |json: &str| {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        ValueSeed::new(&descriptor).deserialize(value).unwrap()
+    };
+    assert_eq!(seed_value(r#""ReadWrite""#), 0x3);
+    assert_eq!(seed_value(r#"["ReadWrite"]"#), 0x3);
+}
+
+#[test]
+fn test_bypass_role_names_round_trip() {
+    let descriptor = CapabilityDescriptor::builder()
+        .capability("Read", 0x1)
+        .capability("Write", 0x2)
+        .capability("Admin", 0x4)
+        .grants_all("Admin")
+        .build()
+        .unwrap();
+    let admin = RoleCapability::new(descriptor.clone(), 0x4);
+
+    // Names list the stored bits, not the capabilities the bypass grants
+    let names = serde_json::to_value(admin.value_as(ValueFormat::Names)).unwrap();
+    assert_eq!(names, serde_json::json!(["Admin"]));
+    let back = RoleCapability::deserialize_value(descriptor, names).unwrap();
+    assert_eq!(back.hex_value, 0x4);
+}
+
+#[test]
+fn test_role_values_in_toml_config() {
+    let descriptor = create_test_descriptor();
+    let roles = [
+        ("admin", RoleCapability::new(descriptor.clone(), 0xF)),
+        ("editor", RoleCapability::new(descriptor.clone(), 0x3)),
+    ];
+
+    let mut config = BTreeMap::new();
+    for (name, role) in &roles {
+        config.insert(*name, role.value_as(ValueFormat::Names));
+    }
+    let text = toml::to_string(&config).unwrap();
+    assert_eq!(
+        text,
+        "admin = [\"Read\", \"Write\", \"Execute\", \"Admin\"]\neditor = [\"Read\", \"Write\"]\n"
+    );
+
+    let loaded: BTreeMap<String, toml::Value> = toml::from_str(&text).unwrap();
+    let editor =
+        RoleCapability::deserialize_value(descriptor.clone(), loaded["editor"].clone()).unwrap();
+    assert_eq!(editor.hex_value, 0x3);
+
+    // Hand-written configs may mix forms
+    let mixed: BTreeMap<String, toml::Value> =
+        toml::from_str("viewer = \"0x1\"\nrunner = 5\nwriter = \"Read|Write\"\n").unwrap();
+    let seed_value = |key: &str| {
+        ValueSeed::new(&descriptor)
+            .deserialize(mixed[key].clone())
+            .unwrap()
+    };
+    assert_eq!(seed_value("viewer"), 0x1);
+    assert_eq!(seed_value("runner"), 0x5);
+    assert_eq!(seed_value("writer"), 0x3);
+}
+
+#[test]
+fn test_role_yaml_round_trip() {
+    let role = RoleCapability::new(create_test_descriptor(), 0x6);
+
+    let yaml = serde_yaml::to_string(&role.serialize_as(ValueFormat::Names)).unwrap();
+    assert!(yaml.contains("- Write\n"));
+    assert!(yaml.contains("- Execute\n"));
+
+    let back: RoleCapability = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(back.hex_value, 0x6);
+
+    let integer_yaml = serde_yaml::to_string(&role.serialize_as(ValueFormat::Integer)).unwrap();
+    let back: RoleCapability = serde_yaml::from_str(&integer_yaml).unwrap();
+    assert_eq!(back.hex_value, 0x6);
+}
+
+#[test]
+fn test_wide_and_bitset_values() {
+    let descriptor = CapabilityDescriptor::<u64>::builder()
+        .capability("Low", 0x1)
+        .capability("High", 1 << 49)
+        .build()
+        .unwrap();
+    let role = RoleCapability::new(descriptor, (1 << 49) | 0x1);
+    let json = serde_json::to_string(&role).unwrap();
+    let back: RoleCapability<u64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.hex_value, role.hex_value);
+
+    let descriptor = (0..200)
+        .fold(
+            CapabilityDescriptor::<CapabilityBitSet>::builder(),
+            |builder, i| builder.next_capability(format!("Capability{}", i)),
+        )
+        .build()
+        .unwrap();
+    let value: CapabilityBitSet = [0, 150].into_iter().collect();
+    let role = RoleCapability::new(descriptor, value.clone());
+    for format in [ValueFormat::Hex, ValueFormat::Integer, ValueFormat::Names] {
+        let json = serde_json::to_string(&role.serialize_as(format)).unwrap();
+        let back: RoleCapability<CapabilityBitSet> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.hex_value, value, "format {:?}", format);
+    }
+}
+
+#[test]
+fn test_value_format_is_configurable() {
+    #[derive(serde::Deserialize)]
+    struct Settings {
+        role_format: ValueFormat,
+    }
+
+    let settings: Settings = toml::from_str("role_format = \"names\"").unwrap();
+    assert_eq!(settings.role_format, ValueFormat::Names);
+    assert_eq!(ValueFormat::default(), ValueFormat::Hex);
+}
+
+#[test]
+fn test_explanation_json() {
+    let descriptor = create_test_descriptor();
+    let everyone = RoleCapability::new(descriptor.clone(), 0x1);
+    let writer = RoleCapability::new(descriptor.clone(), 0x2);
+    let layers = OverwriteLayers::new()
+        .everyone(PermissionOverwrite::new(0x0, 0x2, &descriptor).unwrap())
+        .role(
+            "Writer",
+            PermissionOverwrite::new(0x2, 0x0, &descriptor).unwrap(),
+        );
+
+    let explanation = PermissionCalculator::new(&everyone)
+        .role("Writer", &writer)
+        .overwrites(&layers)
+        .explain("Write")
+        .unwrap();
+
+    let json = serde_json::to_value(&explanation).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "capability": "Write",
+            "granted": true,
+            "steps": [
+                {"step": "granted", "source": {"role": "Writer"}},
+                {"step": "denied", "layer": "everyone"},
+                {"step": "granted", "source": {"overwrite": {"role": "Writer"}}},
+            ]
+        })
+    );
+
+    let back: Explanation = serde_json::from_value(json).unwrap();
+    assert_eq!(back, explanation);
+}
+
+#[test]
+fn test_domain_permissions_json_round_trip() {
+    let registry = DescriptorRegistry::builder()
+        .domain("server", create_test_descriptor())
+        .domain("channel", create_test_descriptor())
+        .build()
+        .unwrap();
+    let permissions = registry
+        .encode(["server:Admin", "channel:Read", "channel:Write"])
+        .unwrap();
+
+    let json = serde_json::to_string(&permissions).unwrap();
+    assert_eq!(json, r#"{"channel":3,"server":8}"#);
+
+    let back: DomainPermissions = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, permissions);
+    assert!(registry.validate(&back).is_ok());
+
+    let stored: DomainPermissions = serde_json::from_str(r#"{"billing":1}"#).unwrap();
+    assert!(registry.validate(&stored).is_err());
+}